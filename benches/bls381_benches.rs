@@ -4,8 +4,13 @@ extern crate hex;
 extern crate milagro_bls;
 extern crate rand;
 
+use amcl::bls381::big::{Big, MODBYTES};
+use amcl::bls381::ecp::ECP;
+use amcl::bls381::ecp2::ECP2;
+use amcl::bls381::pair::{g1mul, g2mul};
 use criterion::{black_box, criterion_group, criterion_main, Benchmark, Criterion};
 use milagro_bls::*;
+use rand::Rng;
 
 fn compression_signature(c: &mut Criterion) {
     let compressed_g2 = hex::decode("a666d31d7e6561371644eb9ca7dbcb87257d8fd84a09e38a7a491ce0bbac64a324aa26385aebc99f47432970399a2ecb0def2d4be359640e6dae6438119cbdc4f18e5e4496c68a979473a72b72d3badf98464412e9d8f8d2ea9b31953bb24899").unwrap();
@@ -88,8 +93,14 @@ fn signing(c: &mut Criterion) {
     let sk = keypair.sk;
     let pk = keypair.pk;
 
+    let prepared_pk = PreparedPublicKey::from(&pk);
+
     let msg = "Some msg";
     let sig = Signature::new(&msg.as_bytes(), &sk);
+    let prepared_sig = sig.clone();
+    let prehashed_sig = sig.clone();
+    let hashed_message = HashedMessage::new(msg.as_bytes());
+    let prehashed_pk = pk.clone();
 
     c.bench(
         "signing",
@@ -110,6 +121,36 @@ fn signing(c: &mut Criterion) {
         })
         .sample_size(10),
     );
+
+    c.bench(
+        "signing",
+        Benchmark::new("Verify a Signature with a PreparedPublicKey", move |b| {
+            b.iter(|| {
+                black_box(prepared_sig.verify_prepared(&msg.as_bytes(), &prepared_pk));
+            })
+        })
+        .sample_size(10),
+    );
+
+    c.bench(
+        "signing",
+        Benchmark::new("Hash a message to G2", move |b| {
+            b.iter(|| {
+                black_box(HashedMessage::new(&msg.as_bytes()));
+            })
+        })
+        .sample_size(10),
+    );
+
+    c.bench(
+        "signing",
+        Benchmark::new("Verify a Signature with a HashedMessage", move |b| {
+            b.iter(|| {
+                black_box(prehashed_sig.verify_prehashed(&hashed_message, &prehashed_pk));
+            })
+        })
+        .sample_size(10),
+    );
 }
 
 fn aggregation(c: &mut Criterion) {
@@ -244,6 +285,95 @@ fn aggregate_verfication_multiple_signatures(c: &mut Criterion) {
     );
 }
 
+fn batch_verification_rand_bits(c: &mut Criterion) {
+    let mut rng = &mut rand::thread_rng();
+    let n = 10; // Signatures
+    let msgs: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; 32]).collect();
+    let mut aggregate_public_keys: Vec<AggregatePublicKey> = vec![];
+    let mut aggregate_signatures: Vec<AggregateSignature> = vec![];
+
+    for msg in msgs.iter() {
+        let keypair = Keypair::random(&mut rng);
+        let signature = Signature::new(msg, &keypair.sk);
+        aggregate_public_keys.push(AggregatePublicKey::from_public_key(&keypair.pk));
+        aggregate_signatures.push(AggregateSignature::from_signature(&signature));
+    }
+
+    // Compare the minimum 64 bit random scalars with the default 128 bit random scalars
+    for rand_bits in [MIN_RAND_BITS, DEFAULT_RAND_BITS].iter().cloned() {
+        let msgs = msgs.clone();
+        let aggregate_public_keys = aggregate_public_keys.clone();
+        let aggregate_signatures = aggregate_signatures.clone();
+
+        c.bench(
+            "batch-verification-rand-bits-10",
+            Benchmark::new(
+                format!("Verification with {} bit random scalars", rand_bits),
+                move |b| {
+                    b.iter(|| {
+                        let signature_sets = aggregate_signatures
+                            .iter()
+                            .zip(aggregate_public_keys.iter())
+                            .zip(msgs.iter().map(|x| x.as_slice()))
+                            .map(|((a, b), c)| (a, b, c));
+                        black_box(
+                            AggregateSignature::verify_multiple_aggregate_signatures_with_rand_bits(
+                                &mut rand::thread_rng(),
+                                signature_sets,
+                                rand_bits,
+                            ),
+                        );
+                    })
+                },
+            )
+            .sample_size(10),
+        );
+    }
+}
+
+fn multi_scalar_multiplication(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+
+    for n in [64, 512, 4096].iter().cloned() {
+        // Random points and scalars of the size used in batch verification
+        let mut g1_points = vec![];
+        let mut g2_points = vec![];
+        let mut scalars = vec![];
+        for _ in 0..n {
+            let mut bytes = [0u8; MODBYTES];
+            rng.fill(&mut bytes[MODBYTES - 32..]);
+            let point_scalar = Big::from_bytes(&bytes);
+            g1_points.push(g1mul(&ECP::generator(), &point_scalar));
+            g2_points.push(g2mul(&ECP2::generator(), &point_scalar));
+
+            let mut bytes = [0u8; MODBYTES];
+            rng.fill(&mut bytes[MODBYTES - DEFAULT_RAND_BITS / 8..]);
+            scalars.push(Big::from_bytes(&bytes));
+        }
+        let g1_scalars = scalars.clone();
+
+        c.bench(
+            "multi-scalar-multiplication",
+            Benchmark::new(format!("G1 with {} points", n), move |b| {
+                b.iter(|| {
+                    black_box(msm_g1(&g1_points, &g1_scalars));
+                })
+            })
+            .sample_size(10),
+        );
+
+        c.bench(
+            "multi-scalar-multiplication",
+            Benchmark::new(format!("G2 with {} points", n), move |b| {
+                b.iter(|| {
+                    black_box(msm_g2(&g2_points, &scalars));
+                })
+            })
+            .sample_size(10),
+        );
+    }
+}
+
 fn key_generation(c: &mut Criterion) {
     c.bench(
         "key generation",
@@ -265,12 +395,24 @@ fn key_generation(c: &mut Criterion) {
             })
         }),
     );
+
+    let sk = SecretKey::random(&mut rand::thread_rng());
+    c.bench(
+        "key generation",
+        Benchmark::new("Compute the PublicKey of a SecretKey", move |b| {
+            b.iter(|| {
+                black_box(sk.public_key());
+            })
+        }),
+    );
 }
 
 criterion_group!(
     benches,
     signing,
     aggregate_verfication_multiple_signatures,
+    batch_verification_rand_bits,
+    multi_scalar_multiplication,
     aggregate_verfication,
     aggregation,
     compression_signature,
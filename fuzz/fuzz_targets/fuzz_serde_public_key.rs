@@ -6,5 +6,8 @@ fuzz_target!(|data: &[u8]| {
     if let Ok(point) = PublicKey::from_bytes(data) {
         let data_round_trip = point.as_bytes();
         assert_eq!(data.to_vec(), data_round_trip);
+
+        let json = serde_json::to_string(&point).unwrap();
+        assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), point);
     }
 });
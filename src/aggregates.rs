@@ -1,14 +1,34 @@
 extern crate amcl;
 extern crate rand;
 
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
 use super::amcl_utils::{
-    self, ate2_evaluation, compress_g2, decompress_g2, g1mul, g2mul, hash_to_curve_g2, pair,
-    subgroup_check_g2, AmclError, Big, GroupG1, GroupG2, G2_BYTES,
+    self, ate2_evaluation, compress_g1, compress_g2, decompress_g1, decompress_g2, g1mul, g2mul,
+    hash_to_curve_g1, hash_to_curve_g1_with_dst, hash_to_curve_g2, hash_to_curve_g2_with_dst,
+    msm_g1, msm_g2, multi_pairing_is_unity, subgroup_check_g1, subgroup_check_g2, validate_dst,
+    Big, GroupG1, GroupG2, G1_BYTES, G2_BYTES, MODBYTES,
+};
+use super::ciphersuite::{
+    hash_message, hash_message_min_sig, messages_are_distinct, Ciphersuite, ProofOfPossessionScheme,
 };
-use super::keys::PublicKey;
-use super::signature::Signature;
+use super::compressed::{PublicKeyBytes, SignatureBytes};
+use super::error::{Error, VerifyError};
+use super::hashed_message::HashedMessage;
+use super::keys::{sealed, MinSigPublicKey, PreparedPublicKey, PublicKey, VerifyingKey};
+use super::signature::{MinSigSignature, Signature};
 use rand::Rng;
 
+// Batch Verification Constants
+/// Bits of randomness in each scalar of `verify_multiple_aggregate_signatures`, matching the
+/// ~128 bit security level of BLS12-381.
+pub const DEFAULT_RAND_BITS: usize = 128;
+/// Minimum bits of randomness in each batch verification scalar.
+pub const MIN_RAND_BITS: usize = 64;
+/// Maximum bits of randomness in each batch verification scalar, keeping scalars below r.
+pub const MAX_RAND_BITS: usize = 254;
+
 /// Allows for the adding/combining of multiple BLS PublicKeys.
 ///
 /// This may be used to verify some AggregateSignature.
@@ -26,14 +46,14 @@ impl AggregatePublicKey {
     /// This is a helper method combining the `new()` and `add()` functions.
     ///
     /// Pre-requsites: All public keys must be PoP verified before calling this function.
-    pub fn aggregate(keys: &[&PublicKey]) -> Result<Self, AmclError> {
+    pub fn aggregate(keys: &[&PublicKey]) -> Result<Self, Error> {
         if keys.is_empty() {
-            return Err(AmclError::AggregateEmptyPoints);
+            return Err(Error::EmptyAggregate);
         }
 
         let mut agg_key = Self { point: GroupG1::new() };
         for key in keys {
-            agg_key.point.add(&key.point)
+            agg_key.point.add(key.point())
         }
         Ok(agg_key)
     }
@@ -43,9 +63,9 @@ impl AggregatePublicKey {
     /// This is a helper method combining the `new()` and `add()` functions.
     ///
     /// Pre-requsites: All public keys must be PoP verified before calling this function.
-    pub fn into_aggregate(keys: &[PublicKey]) -> Result<Self, AmclError> {
+    pub fn into_aggregate(keys: &[PublicKey]) -> Result<Self, Error> {
         if keys.is_empty() {
-            return Err(AmclError::AggregateEmptyPoints);
+            return Err(Error::EmptyAggregate);
         }
 
         let mut point = GroupG1::new();
@@ -69,12 +89,61 @@ impl AggregatePublicKey {
         self.point.add(&public_key.point);
     }
 
+    /// Instantiate a new aggregate public key from compressed PublicKeys.
+    ///
+    /// Each PublicKey is decompressed and validated, errors if any are invalid.
+    /// Pre-requsites: All public keys must be PoP verified before calling this function.
+    pub fn aggregate_bytes(keys: &[&PublicKeyBytes]) -> Result<Self, Error> {
+        if keys.is_empty() {
+            return Err(Error::EmptyAggregate);
+        }
+
+        let mut agg_key = AggregatePublicKey::from_public_key(&keys[0].decompress()?);
+        for key in &keys[1..] {
+            agg_key.add_bytes(key)?;
+        }
+        Ok(agg_key)
+    }
+
+    /// Add a compressed PublicKey to the AggregatePublicKey.
+    ///
+    /// The PublicKey is decompressed and validated, errors if it is invalid.
+    /// Pre-requsites: Public keys must be PoP verified before calling this function.
+    pub fn add_bytes(&mut self, public_key: &PublicKeyBytes) -> Result<(), Error> {
+        self.add(&public_key.decompress()?);
+        Ok(())
+    }
+
     /// Add a AggregatePublicKey to the AggregatePublicKey.
     pub fn add_aggregate(&mut self, aggregate_public_key: &AggregatePublicKey) {
         // Note: it is possible to have an `AggregatePublicKey = infinity` by summing non-infinity PublicKeys
         // which add to a multiple of the group order.
         self.point.add(&aggregate_public_key.point);
     }
+
+    /// Instantiate an AggregatePublicKey from compressed bytes.
+    ///
+    /// The point must be in the G1 subgroup, infinity is accepted as it may be a valid aggregate.
+    pub fn from_bytes(bytes: &[u8]) -> Result<AggregatePublicKey, Error> {
+        let point = decompress_g1(bytes)?;
+        if !subgroup_check_g1(&point) {
+            return Err(Error::NotInSubgroup);
+        }
+        Ok(Self { point })
+    }
+
+    /// Export the AggregatePublicKey to compressed bytes.
+    pub fn as_bytes(&self) -> [u8; G1_BYTES] {
+        compress_g1(&self.point)
+    }
+}
+
+impl sealed::Sealed for AggregatePublicKey {}
+
+impl VerifyingKey for AggregatePublicKey {
+    fn point(&self) -> &GroupG1 {
+        &self.point
+    }
 }
 
 /// Allows for the adding/combining of multiple BLS Signatures.
@@ -115,6 +184,25 @@ impl AggregateSignature {
         self.point.add(&signature.point);
     }
 
+    /// Instantiate a new AggregateSignature from compressed Signatures.
+    ///
+    /// Errors if any Signature cannot be decompressed.
+    pub fn aggregate_bytes(signatures: &[&SignatureBytes]) -> Result<Self, Error> {
+        let mut aggregate_signature = AggregateSignature::new();
+        for signature in signatures {
+            aggregate_signature.add_bytes(signature)?;
+        }
+        Ok(aggregate_signature)
+    }
+
+    /// Add a compressed Signature to the AggregateSignature.
+    ///
+    /// Errors if the Signature cannot be decompressed.
+    pub fn add_bytes(&mut self, signature: &SignatureBytes) -> Result<(), Error> {
+        self.add(&signature.decompress()?);
+        Ok(())
+    }
+
     /// Add a AggregateSignature to the AggregateSignature.
     ///
     /// To maintain consensus AggregateSignatures should only be added
@@ -126,32 +214,174 @@ impl AggregateSignature {
     /// AggregateVerify
     ///
     /// Verifies an AggregateSignature against a list of Messages and PublicKeys
+    /// Uses the Proof of Possession ciphersuite.
     /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-02#section-3.3
     pub fn aggregate_verify(&self, msgs: &[&[u8]], public_keys: &[&PublicKey]) -> bool {
+        self.try_aggregate_verify(msgs, public_keys).is_ok()
+    }
+
+    /// AggregateVerify
+    ///
+    /// As `aggregate_verify` returning the reason verification failed.
+    pub fn try_aggregate_verify(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&PublicKey],
+    ) -> Result<(), VerifyError> {
+        self.try_aggregate_verify_with_ciphersuite::<ProofOfPossessionScheme>(msgs, public_keys)
+    }
+
+    /// AggregateVerify
+    ///
+    /// As `aggregate_verify` against PreparedPublicKeys.
+    pub fn aggregate_verify_prepared(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&PreparedPublicKey],
+    ) -> bool {
+        self.try_aggregate_verify_prepared(msgs, public_keys).is_ok()
+    }
+
+    /// AggregateVerify
+    ///
+    /// As `aggregate_verify_prepared` returning the reason verification failed.
+    pub fn try_aggregate_verify_prepared(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&PreparedPublicKey],
+    ) -> Result<(), VerifyError> {
+        // Require same number of messages as PublicKeys and >=1 PublicKeys.
+        check_lengths(msgs.len(), public_keys.len())?;
+
+        self.aggregate_verify_hashed(
+            msgs.iter()
+                .zip(public_keys)
+                .map(|(msg, pk)| (hash_message::<ProofOfPossessionScheme>(msg, *pk), *pk)),
+        )
+    }
+
+    /// AggregateVerify
+    ///
+    /// Verifies an AggregateSignature against a list of Messages and PublicKeys using the
+    /// given Ciphersuite. The Basic scheme requires all messages to be distinct.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3
+    pub fn aggregate_verify_with_ciphersuite<C: Ciphersuite>(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&PublicKey],
+    ) -> bool {
+        self.try_aggregate_verify_with_ciphersuite::<C>(msgs, public_keys).is_ok()
+    }
+
+    /// AggregateVerify
+    ///
+    /// As `aggregate_verify_with_ciphersuite` returning the reason verification failed.
+    pub fn try_aggregate_verify_with_ciphersuite<C: Ciphersuite>(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&PublicKey],
+    ) -> Result<(), VerifyError> {
         // Require same number of messages as PublicKeys and >=1 PublicKeys.
-        if msgs.len() != public_keys.len() || public_keys.is_empty() {
-            return false;
+        check_lengths(msgs.len(), public_keys.len())?;
+
+        // Require distinct messages for the Basic scheme
+        if C::DISTINCT_MESSAGES && !messages_are_distinct(msgs) {
+            return Err(VerifyError::DuplicateMessages);
         }
 
+        self.aggregate_verify_hashed(
+            msgs.iter().zip(public_keys).map(|(msg, pk)| (hash_message::<C>(msg, *pk), *pk)),
+        )
+    }
+
+    /// CoreAggregateVerify
+    ///
+    /// Verifies an AggregateSignature against a list of Messages and PublicKeys or
+    /// PreparedPublicKeys using a custom domain separation tag. Returns false if the DST is empty.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-2.9
+    pub fn aggregate_verify_with_dst<K: VerifyingKey>(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&K],
+        dst: &[u8],
+    ) -> bool {
+        self.try_aggregate_verify_with_dst(msgs, public_keys, dst).is_ok()
+    }
+
+    /// CoreAggregateVerify
+    ///
+    /// As `aggregate_verify_with_dst` returning the reason verification failed.
+    pub fn try_aggregate_verify_with_dst<K: VerifyingKey>(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&K],
+        dst: &[u8],
+    ) -> Result<(), VerifyError> {
+        // Require same number of messages as PublicKeys and >=1 PublicKeys.
+        check_lengths(msgs.len(), public_keys.len())?;
+
+        let dst = validate_dst(dst).map_err(|_| VerifyError::InvalidDst)?;
+
+        self.aggregate_verify_hashed(
+            msgs.iter()
+                .zip(public_keys)
+                .map(|(msg, pk)| (hash_to_curve_g2_with_dst(msg, dst), *pk)),
+        )
+    }
+
+    /// CoreAggregateVerify
+    ///
+    /// Verifies an AggregateSignature against a list of messages which have already been hashed
+    /// to curve and PublicKeys or PreparedPublicKeys.
+    pub fn aggregate_verify_prehashed<K: VerifyingKey>(
+        &self,
+        hashed_messages: &[&HashedMessage],
+        public_keys: &[&K],
+    ) -> bool {
+        self.try_aggregate_verify_prehashed(hashed_messages, public_keys).is_ok()
+    }
+
+    /// CoreAggregateVerify
+    ///
+    /// As `aggregate_verify_prehashed` returning the reason verification failed.
+    pub fn try_aggregate_verify_prehashed<K: VerifyingKey>(
+        &self,
+        hashed_messages: &[&HashedMessage],
+        public_keys: &[&K],
+    ) -> Result<(), VerifyError> {
+        // Require same number of messages as PublicKeys and >=1 PublicKeys.
+        check_lengths(hashed_messages.len(), public_keys.len())?;
+
+        self.aggregate_verify_hashed(
+            hashed_messages.iter().zip(public_keys).map(|(msg, pk)| (msg.point().clone(), *pk)),
+        )
+    }
+
+    // AggregateVerify for pairs of (H(msg), PublicKey) where messages are already hashed to curve.
+    fn aggregate_verify_hashed<'a, I, K>(&self, hashed_messages: I) -> Result<(), VerifyError>
+    where
+        I: Iterator<Item = (GroupG2, &'a K)>,
+        K: VerifyingKey + 'a,
+    {
         // Subgroup check for signature
         if !subgroup_check_g2(&self.point) {
-            return false;
+            return Err(VerifyError::SignatureNotInSubgroup);
         }
 
-        // Stores current value of pairings
-        let mut pairing = pair::initmp();
+        // Pairs of points whose pairings are multiplied
+        let mut pairs = vec![];
 
-        for (i, pk) in public_keys.iter().enumerate() {
-            // Hash message to curve
-            let mut msg_hash = hash_to_curve_g2(msgs[i]);
+        for (mut msg_hash, pk) in hashed_messages {
+            // PreparedPublicKeys may be the point at infinity
+            if pk.point().is_infinity() {
+                return Err(VerifyError::InfinityPublicKey);
+            }
 
             // Points must be affine for pairing
-            let mut pk_affine = pk.point.clone();
-            pk_affine.affine();
             msg_hash.affine();
 
             // pairing *= e(H(msg[i], pk[i]))
-            pair::another(&mut pairing, &msg_hash, &pk_affine);
+            pairs.push((msg_hash, pk.affine_point().into_owned()));
         }
 
         // Affine for signature
@@ -161,83 +391,946 @@ impl AggregateSignature {
         generator_g1_negative.neg(); // already affine
 
         // pairing *= e(signature, G1)
-        pair::another(&mut pairing, &sig_point, &generator_g1_negative);
+        pairs.push((sig_point, generator_g1_negative));
 
         // Complete pairing and verify output is 1.
-        let mut v = pair::miller(&pairing);
-        v = pair::fexp(&v);
-        v.is_unity()
+        pairing_result(multi_pairing_is_unity(&pairs))
     }
 
     /// FastAggregateVerify
     ///
     /// Verifies an AggregateSignature against a list of PublicKeys.
     /// PublicKeys must all be verified via Proof of Possession before running this function.
+    /// Only defined for the Proof of Possession ciphersuite.
     /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-02#section-3.3.4
     pub fn fast_aggregate_verify(&self, msg: &[u8], public_keys: &[&PublicKey]) -> bool {
+        self.try_fast_aggregate_verify(msg, public_keys).is_ok()
+    }
+
+    /// FastAggregateVerify
+    ///
+    /// As `fast_aggregate_verify` returning the reason verification failed.
+    pub fn try_fast_aggregate_verify(
+        &self,
+        msg: &[u8],
+        public_keys: &[&PublicKey],
+    ) -> Result<(), VerifyError> {
         // Require at least one PublicKey
-        if public_keys.is_empty() {
-            return false;
-        }
+        let aggregate_public_key =
+            AggregatePublicKey::aggregate(public_keys).map_err(|_| VerifyError::EmptyInput)?;
+
+        self.fast_aggregate_verify_hashed(hash_to_curve_g2(msg), &aggregate_public_key)
+    }
+
+    /// FastAggregateVerify
+    ///
+    /// Verifies an AggregateSignature against a list of PublicKeys using a custom
+    /// domain separation tag. Returns false if the DST is empty.
+    /// PublicKeys must all be verified via Proof of Possession before running this function.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3.3.4
+    pub fn fast_aggregate_verify_with_dst(
+        &self,
+        msg: &[u8],
+        public_keys: &[&PublicKey],
+        dst: &[u8],
+    ) -> bool {
+        self.try_fast_aggregate_verify_with_dst(msg, public_keys, dst).is_ok()
+    }
+
+    /// FastAggregateVerify
+    ///
+    /// As `fast_aggregate_verify_with_dst` returning the reason verification failed.
+    pub fn try_fast_aggregate_verify_with_dst(
+        &self,
+        msg: &[u8],
+        public_keys: &[&PublicKey],
+        dst: &[u8],
+    ) -> Result<(), VerifyError> {
+        // Require at least one PublicKey
+        let aggregate_public_key =
+            AggregatePublicKey::aggregate(public_keys).map_err(|_| VerifyError::EmptyInput)?;
+
+        self.try_fast_aggregate_verify_pre_aggregated_with_dst(msg, &aggregate_public_key, dst)
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// Verifies an AggregateSignature against an AggregatePublicKey.
+    /// PublicKeys must all be verified before being aggregated.
+    /// Differs to IEFT FastAggregateVerify in that public keys are already aggregated.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-02#section-3.3.4
+    pub fn fast_aggregate_verify_pre_aggregated(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &AggregatePublicKey,
+    ) -> bool {
+        self.try_fast_aggregate_verify_pre_aggregated(msg, aggregate_public_key).is_ok()
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// As `fast_aggregate_verify_pre_aggregated` returning the reason verification failed.
+    pub fn try_fast_aggregate_verify_pre_aggregated(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &AggregatePublicKey,
+    ) -> Result<(), VerifyError> {
+        self.fast_aggregate_verify_hashed(hash_to_curve_g2(msg), aggregate_public_key)
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// As `fast_aggregate_verify_pre_aggregated` against a PreparedPublicKey of the
+    /// AggregatePublicKey.
+    pub fn fast_aggregate_verify_prepared(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &PreparedPublicKey,
+    ) -> bool {
+        self.try_fast_aggregate_verify_prepared(msg, aggregate_public_key).is_ok()
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// As `fast_aggregate_verify_prepared` returning the reason verification failed.
+    pub fn try_fast_aggregate_verify_prepared(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &PreparedPublicKey,
+    ) -> Result<(), VerifyError> {
+        self.fast_aggregate_verify_hashed(hash_to_curve_g2(msg), aggregate_public_key)
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// Verifies an AggregateSignature against an AggregatePublicKey, or a PreparedPublicKey of
+    /// one, using a custom domain separation tag. Returns false if the DST is empty.
+    /// PublicKeys must all be verified before being aggregated.
+    pub fn fast_aggregate_verify_pre_aggregated_with_dst<K: VerifyingKey>(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &K,
+        dst: &[u8],
+    ) -> bool {
+        self.try_fast_aggregate_verify_pre_aggregated_with_dst(msg, aggregate_public_key, dst)
+            .is_ok()
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// As `fast_aggregate_verify_pre_aggregated_with_dst` returning the reason verification
+    /// failed.
+    pub fn try_fast_aggregate_verify_pre_aggregated_with_dst<K: VerifyingKey>(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &K,
+        dst: &[u8],
+    ) -> Result<(), VerifyError> {
+        let dst = validate_dst(dst).map_err(|_| VerifyError::InvalidDst)?;
+        self.fast_aggregate_verify_hashed(hash_to_curve_g2_with_dst(msg, dst), aggregate_public_key)
+    }
+
+    /// FastAggregateVerify
+    ///
+    /// Verifies an AggregateSignature against a message which has already been hashed to curve
+    /// and a list of PublicKeys.
+    /// PublicKeys must all be verified via Proof of Possession before running this function.
+    pub fn fast_aggregate_verify_prehashed(
+        &self,
+        hashed_message: &HashedMessage,
+        public_keys: &[&PublicKey],
+    ) -> bool {
+        self.try_fast_aggregate_verify_prehashed(hashed_message, public_keys).is_ok()
+    }
+
+    /// FastAggregateVerify
+    ///
+    /// As `fast_aggregate_verify_prehashed` returning the reason verification failed.
+    pub fn try_fast_aggregate_verify_prehashed(
+        &self,
+        hashed_message: &HashedMessage,
+        public_keys: &[&PublicKey],
+    ) -> Result<(), VerifyError> {
+        // Require at least one PublicKey
+        let aggregate_public_key =
+            AggregatePublicKey::aggregate(public_keys).map_err(|_| VerifyError::EmptyInput)?;
+
+        self.try_fast_aggregate_verify_pre_aggregated_prehashed(
+            hashed_message,
+            &aggregate_public_key,
+        )
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// Verifies an AggregateSignature against a message which has already been hashed to curve
+    /// and an AggregatePublicKey, or a PreparedPublicKey of one.
+    /// PublicKeys must all be verified before being aggregated.
+    pub fn fast_aggregate_verify_pre_aggregated_prehashed<K: VerifyingKey>(
+        &self,
+        hashed_message: &HashedMessage,
+        aggregate_public_key: &K,
+    ) -> bool {
+        self.try_fast_aggregate_verify_pre_aggregated_prehashed(
+            hashed_message,
+            aggregate_public_key,
+        )
+        .is_ok()
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// As `fast_aggregate_verify_pre_aggregated_prehashed` returning the reason verification
+    /// failed.
+    pub fn try_fast_aggregate_verify_pre_aggregated_prehashed<K: VerifyingKey>(
+        &self,
+        hashed_message: &HashedMessage,
+        aggregate_public_key: &K,
+    ) -> Result<(), VerifyError> {
+        self.fast_aggregate_verify_hashed(hashed_message.point().clone(), aggregate_public_key)
+    }
 
+    // FastAggregateVerify for a message which has already been hashed to curve.
+    fn fast_aggregate_verify_hashed(
+        &self,
+        mut msg_hash: GroupG2,
+        aggregate_public_key: &impl VerifyingKey,
+    ) -> Result<(), VerifyError> {
         // Subgroup check for signature
         if !subgroup_check_g2(&self.point) {
-            return false;
-        }
-
-        // Aggregate PublicKeys
-        let aggregate_public_key = AggregatePublicKey::aggregate(public_keys);
-        if aggregate_public_key.is_err() {
-            return false;
+            return Err(VerifyError::SignatureNotInSubgroup);
         }
-        let aggregate_public_key = aggregate_public_key.unwrap();
 
         // Ensure AggregatePublicKey is not infinity
-        if aggregate_public_key.point.is_infinity() {
-            return false;
+        if aggregate_public_key.point().is_infinity() {
+            return Err(VerifyError::InfinityPublicKey);
         }
 
-        // Hash message to curve
-        let mut msg_hash = hash_to_curve_g2(msg);
-
         // Points must be affine for pairing
         let mut sig_point = self.point.clone();
-        let mut key_point = aggregate_public_key.point;
+        let key_point = aggregate_public_key.affine_point();
         sig_point.affine();
-        key_point.affine();
         msg_hash.affine();
 
         let mut generator_g1_negative = amcl_utils::GroupG1::generator();
         generator_g1_negative.neg(); // already affine
 
         // Faster ate2 evaualtion checks e(S, -G1) * e(H, PK) == 1
-        ate2_evaluation(&sig_point, &generator_g1_negative, &msg_hash, &key_point)
+        pairing_result(ate2_evaluation(&sig_point, &generator_g1_negative, &msg_hash, &key_point))
     }
 
-    /// FastAggregateVerify - pre-aggregated PublicKeys
+    /// Verify Multiple AggregateSignatures
     ///
-    /// Verifies an AggregateSignature against an AggregatePublicKey.
-    /// PublicKeys must all be verified before being aggregated.
-    /// Differs to IEFT FastAggregateVerify in that public keys are already aggregated.
-    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-02#section-3.3.4
-    pub fn fast_aggregate_verify_pre_aggregated(
-        &self,
-        msg: &[u8],
-        aggregate_public_key: &AggregatePublicKey,
-    ) -> bool {
-        // Subgroup check for signature
-        if !subgroup_check_g2(&self.point) {
-            return false;
+    /// Input (AggregateSignature, PublicKey[m], Message(Vec<u8>))[n]
+    /// Checks that each AggregateSignature is valid with a reduced number of pairings.
+    /// https://ethresear.ch/t/fast-verification-of-multiple-bls-signatures/5407
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn verify_multiple_aggregate_signatures<'a, R, I>(rng: &mut R, signature_sets: I) -> bool
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a AggregatePublicKey, &'a [u8])>,
+    {
+        Self::try_verify_multiple_aggregate_signatures(rng, signature_sets).is_ok()
+    }
+
+    /// Verify Multiple AggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures` returning the reason verification failed.
+    /// A `PairingMismatch` does not identify which sets are invalid, see
+    /// `find_invalid_signature_sets`.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn try_verify_multiple_aggregate_signatures<'a, R, I>(
+        rng: &mut R,
+        signature_sets: I,
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a AggregatePublicKey, &'a [u8])>,
+    {
+        Self::try_verify_multiple_aggregate_signatures_with_rand_bits(
+            rng,
+            signature_sets,
+            DEFAULT_RAND_BITS,
+        )
+    }
+
+    /// Verify Multiple AggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures` against PreparedPublicKeys.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn verify_multiple_aggregate_signatures_prepared<'a, R, I>(
+        rng: &mut R,
+        signature_sets: I,
+    ) -> bool
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a PreparedPublicKey, &'a [u8])>,
+    {
+        Self::try_verify_multiple_aggregate_signatures_prepared(rng, signature_sets).is_ok()
+    }
+
+    /// Verify Multiple AggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures_prepared` returning the reason verification
+    /// failed.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn try_verify_multiple_aggregate_signatures_prepared<'a, R, I>(
+        rng: &mut R,
+        signature_sets: I,
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a PreparedPublicKey, &'a [u8])>,
+    {
+        Self::verify_multiple_hashed(rng, signature_sets, hash_to_curve_g2, DEFAULT_RAND_BITS)
+    }
+
+    /// Verify Multiple AggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures` using random scalars of `rand_bits` bits, an
+    /// invalid batch passes with probability at most 2^-(rand_bits - 1). Returns false if
+    /// `rand_bits` is less than `MIN_RAND_BITS` or greater than `MAX_RAND_BITS`.
+    /// Accepts AggregatePublicKeys or PreparedPublicKeys.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn verify_multiple_aggregate_signatures_with_rand_bits<'a, R, I, K>(
+        rng: &mut R,
+        signature_sets: I,
+        rand_bits: usize,
+    ) -> bool
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a K, &'a [u8])>,
+        K: VerifyingKey + 'a,
+    {
+        Self::try_verify_multiple_aggregate_signatures_with_rand_bits(
+            rng,
+            signature_sets,
+            rand_bits,
+        )
+        .is_ok()
+    }
+
+    /// Verify Multiple AggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures_with_rand_bits` returning the reason
+    /// verification failed.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn try_verify_multiple_aggregate_signatures_with_rand_bits<'a, R, I, K>(
+        rng: &mut R,
+        signature_sets: I,
+        rand_bits: usize,
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a K, &'a [u8])>,
+        K: VerifyingKey + 'a,
+    {
+        Self::verify_multiple_hashed(rng, signature_sets, hash_to_curve_g2, rand_bits)
+    }
+
+    /// Verify Multiple AggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures` where all messages were signed using a custom
+    /// domain separation tag. Returns false if the DST is empty.
+    /// Accepts AggregatePublicKeys or PreparedPublicKeys.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn verify_multiple_aggregate_signatures_with_dst<'a, R, I, K>(
+        rng: &mut R,
+        signature_sets: I,
+        dst: &[u8],
+    ) -> bool
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a K, &'a [u8])>,
+        K: VerifyingKey + 'a,
+    {
+        Self::try_verify_multiple_aggregate_signatures_with_dst(rng, signature_sets, dst).is_ok()
+    }
+
+    /// Verify Multiple AggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures_with_dst` returning the reason verification
+    /// failed.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn try_verify_multiple_aggregate_signatures_with_dst<'a, R, I, K>(
+        rng: &mut R,
+        signature_sets: I,
+        dst: &[u8],
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a K, &'a [u8])>,
+        K: VerifyingKey + 'a,
+    {
+        let dst = validate_dst(dst).map_err(|_| VerifyError::InvalidDst)?;
+
+        Self::verify_multiple_hashed(
+            rng,
+            signature_sets,
+            |msg| hash_to_curve_g2_with_dst(msg, dst),
+            DEFAULT_RAND_BITS,
+        )
+    }
+
+    /// Verify Multiple AggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures` where all messages have already been hashed
+    /// to curve. Accepts AggregatePublicKeys or PreparedPublicKeys.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn verify_multiple_aggregate_signatures_prehashed<'a, R, I, K>(
+        rng: &mut R,
+        signature_sets: I,
+    ) -> bool
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a K, &'a HashedMessage)>,
+        K: VerifyingKey + 'a,
+    {
+        Self::try_verify_multiple_aggregate_signatures_prehashed(rng, signature_sets).is_ok()
+    }
+
+    /// Verify Multiple AggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures_prehashed` returning the reason verification
+    /// failed.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn try_verify_multiple_aggregate_signatures_prehashed<'a, R, I, K>(
+        rng: &mut R,
+        signature_sets: I,
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a K, &'a HashedMessage)>,
+        K: VerifyingKey + 'a,
+    {
+        Self::verify_multiple_hashed(
+            rng,
+            signature_sets,
+            |msg| msg.point().clone(),
+            DEFAULT_RAND_BITS,
+        )
+    }
+
+    /// Find Invalid AggregateSignatures
+    ///
+    /// Input (AggregateSignature, PublicKey[m], Message(Vec<u8>))[n]
+    /// Returns the indices of the invalid signature sets, or an empty Vec if all are valid.
+    /// Failing batches are bisected with `verify_multiple_aggregate_signatures` so that few
+    /// invalid sets require few pairings.
+    /// https://eprint.iacr.org/2010/227
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn find_invalid_signature_sets<'a, R, I>(rng: &mut R, signature_sets: I) -> Vec<usize>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a AggregatePublicKey, &'a [u8])>,
+    {
+        let mut invalid = vec![];
+        let mut hashed_sets = vec![];
+        for (i, (aggregate_signature, aggregate_public_key, msg)) in signature_sets.enumerate() {
+            // Signatures outside the subgroup and infinity AggregatePublicKeys are invalid without
+            // any pairings
+            if subgroup_check_g2(&aggregate_signature.point)
+                && !aggregate_public_key.point.is_infinity()
+            {
+                hashed_sets.push((
+                    i,
+                    aggregate_signature,
+                    aggregate_public_key,
+                    hash_to_curve_g2(msg),
+                ));
+            } else {
+                invalid.push(i);
+            }
+        }
+
+        Self::bisect_invalid(rng, &hashed_sets, false, &mut invalid);
+        invalid.sort_unstable();
+        invalid
+    }
+
+    // Recursively halve a batch until each invalid signature set is isolated.
+    //
+    // If the first half of a failing batch is valid then the second half is known to be invalid
+    // and its verification is skipped.
+    fn bisect_invalid<R: Rng + ?Sized, K: VerifyingKey>(
+        rng: &mut R,
+        signature_sets: &[(usize, &AggregateSignature, &K, GroupG2)],
+        known_invalid: bool,
+        invalid: &mut Vec<usize>,
+    ) {
+        if signature_sets.is_empty() {
+            return;
+        }
+        if !known_invalid
+            && Self::verify_random_linear_combination(
+                rng,
+                signature_sets.iter().map(|(_, sig, key, msg_hash)| (*sig, *key, msg_hash.clone())),
+                DEFAULT_RAND_BITS,
+            )
+            .is_ok()
+        {
+            return;
+        }
+        if signature_sets.len() == 1 {
+            invalid.push(signature_sets[0].0);
+            return;
+        }
+
+        let (left, right) = signature_sets.split_at(signature_sets.len() / 2);
+        let invalid_count = invalid.len();
+        Self::bisect_invalid(rng, left, false, invalid);
+        Self::bisect_invalid(rng, right, invalid.len() == invalid_count, invalid);
+    }
+
+    // Verify Multiple AggregateSignatures where messages are hashed to curve by `hash_message`.
+    //
+    // Every signature set is checked before any message is hashed so that an invalid set costs
+    // no hashing or pairings wherever it is in the batch.
+    fn verify_multiple_hashed<'a, R, I, K, M, H>(
+        rng: &mut R,
+        signature_sets: I,
+        hash_message: H,
+        rand_bits: usize,
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a K, M)>,
+        K: VerifyingKey + 'a,
+        H: Fn(M) -> GroupG2,
+    {
+        let signature_sets: Vec<_> = signature_sets.collect();
+        for (aggregate_signature, aggregate_public_key, _) in signature_sets.iter() {
+            // Verify subgroup of each aggregate_signature
+            if !subgroup_check_g2(&aggregate_signature.point) {
+                return Err(VerifyError::SignatureNotInSubgroup);
+            }
+
+            // Ensure AggregatePublicKey is not infinity
+            if aggregate_public_key.point().is_infinity() {
+                return Err(VerifyError::InfinityPublicKey);
+            }
+        }
+
+        Self::verify_random_linear_combination(
+            rng,
+            signature_sets.into_iter().map(|(sig, key, msg)| (sig, key, hash_message(msg))),
+            rand_bits,
+        )
+    }
+
+    // Verify a random linear combination of signature sets whose signatures are in the subgroup
+    // and whose AggregatePublicKeys are not infinity.
+    fn verify_random_linear_combination<'a, R, I, K>(
+        rng: &mut R,
+        signature_sets: I,
+        rand_bits: usize,
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a AggregateSignature, &'a K, GroupG2)>,
+        K: VerifyingKey + 'a,
+    {
+        if !(MIN_RAND_BITS..=MAX_RAND_BITS).contains(&rand_bits) {
+            return Err(VerifyError::InvalidRandBits);
+        }
+
+        // AggregateSignature[i] and rand[i] for all AggregateSignatures
+        let mut signature_points = vec![];
+        let mut rands = vec![];
+
+        // Pairs of points whose pairings are multiplied
+        let mut pairs = vec![];
+
+        for (aggregate_signature, aggregate_public_key, mut msg_hash) in signature_sets {
+            // Create random offset - rand[i]
+            let rand = random_scalar(rng, rand_bits);
+
+            // rand[i] * Apk[i]
+            let mut aggregate_public_key = g1mul(aggregate_public_key.point(), &rand);
+
+            // Points must be affine before pairings
+            msg_hash.affine();
+            aggregate_public_key.affine();
+
+            // Update current pairings: *= e(H(message[i]), rand[i] * Apk[i])
+            pairs.push((msg_hash, aggregate_public_key));
+
+            signature_points.push(aggregate_signature.point.clone());
+            rands.push(rand);
+        }
+
+        // Sum of (AggregateSignature[i] * rand[i]) for all AggregateSignatures - S'
+        let mut final_agg_sig = msm_g2(&signature_points, &rands);
+
+        // Pairing for LHS - e(As', G1)
+        let mut negative_g1 = GroupG1::generator();
+        negative_g1.neg(); // will be affine
+        final_agg_sig.affine();
+        pairs.push((final_agg_sig, negative_g1));
+
+        // Complete pairing and verify output is 1.
+        pairing_result(multi_pairing_is_unity(&pairs))
+    }
+
+    /// Instatiate an AggregateSignature from some bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<AggregateSignature, Error> {
+        let point = decompress_g2(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// Export (serialize) the AggregateSignature to bytes.
+    pub fn as_bytes(&self) -> [u8; G2_BYTES] {
+        compress_g2(&self.point)
+    }
+}
+
+impl Default for AggregateSignature {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Allows for the adding/combining of multiple MinSigPublicKeys.
+///
+/// This may be used to verify some MinSigAggregateSignature.
+///
+/// MinSigAggregatePublicKeys MUST only be use after PoP Verifying the individual PublicKeys with
+/// `MinSigPublicKey::pop_verify`.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MinSigAggregatePublicKey {
+    pub point: GroupG2,
+}
+
+impl MinSigAggregatePublicKey {
+    /// Instantiate a new aggregate public key from a vector of MinSigPublicKeys.
+    ///
+    /// Pre-requsites: All public keys must be PoP verified before calling this function.
+    pub fn aggregate(keys: &[&MinSigPublicKey]) -> Result<Self, Error> {
+        if keys.is_empty() {
+            return Err(Error::EmptyAggregate);
+        }
+
+        let mut agg_key = Self { point: GroupG2::new() };
+        for key in keys {
+            agg_key.point.add(&key.point);
+        }
+        Ok(agg_key)
+    }
+
+    /// Instantiate a new aggregate public key from a single MinSigPublicKey.
+    ///
+    /// Pre-requsites: Public key must be PoP verified before calling this function.
+    pub fn from_public_key(key: &MinSigPublicKey) -> Self {
+        MinSigAggregatePublicKey { point: key.point.clone() }
+    }
+
+    /// Add a MinSigPublicKey to the MinSigAggregatePublicKey.
+    ///
+    /// Pre-requsites: Public keys must be PoP verified before calling this function.
+    pub fn add(&mut self, public_key: &MinSigPublicKey) {
+        self.point.add(&public_key.point);
+    }
+
+    /// Add a MinSigAggregatePublicKey to the MinSigAggregatePublicKey.
+    pub fn add_aggregate(&mut self, aggregate_public_key: &MinSigAggregatePublicKey) {
+        self.point.add(&aggregate_public_key.point);
+    }
+}
+
+/// Allows for the adding/combining of multiple MinSigSignatures.
+///
+/// This may be verified against some MinSigAggregatePublicKey.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MinSigAggregateSignature {
+    pub point: GroupG1,
+}
+
+impl MinSigAggregateSignature {
+    /// Instantiates a new MinSigAggregateSignature.
+    ///
+    /// The underlying point will be set to infinity.
+    pub fn new() -> Self {
+        Self { point: GroupG1::new() }
+    }
+
+    /// Instantiate a new MinSigAggregateSignature from a vector of MinSigSignatures.
+    pub fn aggregate(signatures: &[&MinSigSignature]) -> Self {
+        let mut aggregate_signature = MinSigAggregateSignature::new();
+        for sig in signatures {
+            aggregate_signature.point.add(&sig.point);
+        }
+        aggregate_signature
+    }
+
+    /// Instantiate a new MinSigAggregateSignature from a single MinSigSignature.
+    pub fn from_signature(signature: &MinSigSignature) -> Self {
+        MinSigAggregateSignature { point: signature.point.clone() }
+    }
+
+    /// Add a MinSigSignature to the MinSigAggregateSignature.
+    pub fn add(&mut self, signature: &MinSigSignature) {
+        self.point.add(&signature.point);
+    }
+
+    /// Add a MinSigAggregateSignature to the MinSigAggregateSignature.
+    pub fn add_aggregate(&mut self, aggregate_signature: &MinSigAggregateSignature) {
+        self.point.add(&aggregate_signature.point);
+    }
+
+    /// AggregateVerify
+    ///
+    /// Verifies a MinSigAggregateSignature against a list of Messages and MinSigPublicKeys.
+    /// Uses the Proof of Possession ciphersuite.
+    pub fn aggregate_verify(&self, msgs: &[&[u8]], public_keys: &[&MinSigPublicKey]) -> bool {
+        self.try_aggregate_verify(msgs, public_keys).is_ok()
+    }
+
+    /// AggregateVerify
+    ///
+    /// As `aggregate_verify` returning the reason verification failed.
+    pub fn try_aggregate_verify(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&MinSigPublicKey],
+    ) -> Result<(), VerifyError> {
+        self.try_aggregate_verify_with_ciphersuite::<ProofOfPossessionScheme>(msgs, public_keys)
+    }
+
+    /// AggregateVerify
+    ///
+    /// Verifies a MinSigAggregateSignature against a list of Messages and MinSigPublicKeys
+    /// using the given Ciphersuite. The Basic scheme requires all messages to be distinct.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3
+    pub fn aggregate_verify_with_ciphersuite<C: Ciphersuite>(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&MinSigPublicKey],
+    ) -> bool {
+        self.try_aggregate_verify_with_ciphersuite::<C>(msgs, public_keys).is_ok()
+    }
+
+    /// AggregateVerify
+    ///
+    /// As `aggregate_verify_with_ciphersuite` returning the reason verification failed.
+    pub fn try_aggregate_verify_with_ciphersuite<C: Ciphersuite>(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&MinSigPublicKey],
+    ) -> Result<(), VerifyError> {
+        // Require same number of messages as PublicKeys and >=1 PublicKeys.
+        check_lengths(msgs.len(), public_keys.len())?;
+
+        // Require distinct messages for the Basic scheme
+        if C::DISTINCT_MESSAGES && !messages_are_distinct(msgs) {
+            return Err(VerifyError::DuplicateMessages);
+        }
+
+        self.aggregate_verify_hashed(
+            msgs.iter().zip(public_keys).map(|(msg, pk)| (hash_message_min_sig::<C>(msg, pk), *pk)),
+        )
+    }
+
+    /// CoreAggregateVerify
+    ///
+    /// Verifies a MinSigAggregateSignature against a list of Messages and MinSigPublicKeys
+    /// using a custom domain separation tag. Returns false if the DST is empty.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-2.9
+    pub fn aggregate_verify_with_dst(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&MinSigPublicKey],
+        dst: &[u8],
+    ) -> bool {
+        self.try_aggregate_verify_with_dst(msgs, public_keys, dst).is_ok()
+    }
+
+    /// CoreAggregateVerify
+    ///
+    /// As `aggregate_verify_with_dst` returning the reason verification failed.
+    pub fn try_aggregate_verify_with_dst(
+        &self,
+        msgs: &[&[u8]],
+        public_keys: &[&MinSigPublicKey],
+        dst: &[u8],
+    ) -> Result<(), VerifyError> {
+        // Require same number of messages as PublicKeys and >=1 PublicKeys.
+        check_lengths(msgs.len(), public_keys.len())?;
+
+        let dst = validate_dst(dst).map_err(|_| VerifyError::InvalidDst)?;
+
+        self.aggregate_verify_hashed(
+            msgs.iter()
+                .zip(public_keys)
+                .map(|(msg, pk)| (hash_to_curve_g1_with_dst(msg, dst), *pk)),
+        )
+    }
+
+    // AggregateVerify for pairs of (H(msg), MinSigPublicKey) where messages are already hashed
+    // to curve.
+    fn aggregate_verify_hashed<'a, I>(&self, hashed_messages: I) -> Result<(), VerifyError>
+    where
+        I: Iterator<Item = (GroupG1, &'a MinSigPublicKey)>,
+    {
+        // Subgroup check for signature
+        if !subgroup_check_g1(&self.point) {
+            return Err(VerifyError::SignatureNotInSubgroup);
+        }
+
+        // Pairs of points whose pairings are multiplied
+        let mut pairs = vec![];
+
+        for (mut msg_hash, pk) in hashed_messages {
+            // MinSigPublicKeys from `from_bytes_unchecked` may be the point at infinity
+            if pk.point.is_infinity() {
+                return Err(VerifyError::InfinityPublicKey);
+            }
+
+            // Points must be affine for pairing
+            let mut pk_affine = pk.point.clone();
+            pk_affine.affine();
+            msg_hash.affine();
+
+            // pairing *= e(pk[i], H(msg[i]))
+            pairs.push((pk_affine, msg_hash));
+        }
+
+        // Affine for signature
+        let mut sig_point = self.point.clone();
+        let mut generator_g2_negative = GroupG2::generator();
+        sig_point.affine();
+        generator_g2_negative.neg(); // already affine
+
+        // pairing *= e(-G2, signature)
+        pairs.push((generator_g2_negative, sig_point));
+
+        // Complete pairing and verify output is 1.
+        pairing_result(multi_pairing_is_unity(&pairs))
+    }
+
+    /// FastAggregateVerify
+    ///
+    /// Verifies a MinSigAggregateSignature against a list of MinSigPublicKeys.
+    /// PublicKeys must all be verified via MinSigProofOfPossession before running this function.
+    /// Only defined for the Proof of Possession ciphersuite.
+    pub fn fast_aggregate_verify(&self, msg: &[u8], public_keys: &[&MinSigPublicKey]) -> bool {
+        self.try_fast_aggregate_verify(msg, public_keys).is_ok()
+    }
+
+    /// FastAggregateVerify
+    ///
+    /// As `fast_aggregate_verify` returning the reason verification failed.
+    pub fn try_fast_aggregate_verify(
+        &self,
+        msg: &[u8],
+        public_keys: &[&MinSigPublicKey],
+    ) -> Result<(), VerifyError> {
+        // Require at least one PublicKey
+        let aggregate_public_key = MinSigAggregatePublicKey::aggregate(public_keys)
+            .map_err(|_| VerifyError::EmptyInput)?;
+
+        self.fast_aggregate_verify_hashed(hash_to_curve_g1(msg), &aggregate_public_key)
+    }
+
+    /// FastAggregateVerify
+    ///
+    /// Verifies a MinSigAggregateSignature against a list of MinSigPublicKeys using a custom
+    /// domain separation tag. Returns false if the DST is empty.
+    /// PublicKeys must all be verified via Proof of Possession before running this function.
+    pub fn fast_aggregate_verify_with_dst(
+        &self,
+        msg: &[u8],
+        public_keys: &[&MinSigPublicKey],
+        dst: &[u8],
+    ) -> bool {
+        self.try_fast_aggregate_verify_with_dst(msg, public_keys, dst).is_ok()
+    }
+
+    /// FastAggregateVerify
+    ///
+    /// As `fast_aggregate_verify_with_dst` returning the reason verification failed.
+    pub fn try_fast_aggregate_verify_with_dst(
+        &self,
+        msg: &[u8],
+        public_keys: &[&MinSigPublicKey],
+        dst: &[u8],
+    ) -> Result<(), VerifyError> {
+        // Require at least one PublicKey
+        let aggregate_public_key = MinSigAggregatePublicKey::aggregate(public_keys)
+            .map_err(|_| VerifyError::EmptyInput)?;
+
+        self.try_fast_aggregate_verify_pre_aggregated_with_dst(msg, &aggregate_public_key, dst)
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// Verifies a MinSigAggregateSignature against a MinSigAggregatePublicKey.
+    /// PublicKeys must all be verified before being aggregated.
+    pub fn fast_aggregate_verify_pre_aggregated(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &MinSigAggregatePublicKey,
+    ) -> bool {
+        self.try_fast_aggregate_verify_pre_aggregated(msg, aggregate_public_key).is_ok()
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// As `fast_aggregate_verify_pre_aggregated` returning the reason verification failed.
+    pub fn try_fast_aggregate_verify_pre_aggregated(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &MinSigAggregatePublicKey,
+    ) -> Result<(), VerifyError> {
+        self.fast_aggregate_verify_hashed(hash_to_curve_g1(msg), aggregate_public_key)
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// Verifies a MinSigAggregateSignature against a MinSigAggregatePublicKey using a custom
+    /// domain separation tag. Returns false if the DST is empty.
+    /// PublicKeys must all be verified before being aggregated.
+    pub fn fast_aggregate_verify_pre_aggregated_with_dst(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &MinSigAggregatePublicKey,
+        dst: &[u8],
+    ) -> bool {
+        self.try_fast_aggregate_verify_pre_aggregated_with_dst(msg, aggregate_public_key, dst)
+            .is_ok()
+    }
+
+    /// FastAggregateVerify - pre-aggregated PublicKeys
+    ///
+    /// As `fast_aggregate_verify_pre_aggregated_with_dst` returning the reason verification
+    /// failed.
+    pub fn try_fast_aggregate_verify_pre_aggregated_with_dst(
+        &self,
+        msg: &[u8],
+        aggregate_public_key: &MinSigAggregatePublicKey,
+        dst: &[u8],
+    ) -> Result<(), VerifyError> {
+        let dst = validate_dst(dst).map_err(|_| VerifyError::InvalidDst)?;
+        self.fast_aggregate_verify_hashed(hash_to_curve_g1_with_dst(msg, dst), aggregate_public_key)
+    }
+
+    // FastAggregateVerify for a message which has already been hashed to curve.
+    fn fast_aggregate_verify_hashed(
+        &self,
+        mut msg_hash: GroupG1,
+        aggregate_public_key: &MinSigAggregatePublicKey,
+    ) -> Result<(), VerifyError> {
+        // Subgroup check for signature
+        if !subgroup_check_g1(&self.point) {
+            return Err(VerifyError::SignatureNotInSubgroup);
         }
 
-        // Ensure AggregatePublicKey is not infinity
+        // Ensure MinSigAggregatePublicKey is not infinity
         if aggregate_public_key.point.is_infinity() {
-            return false;
+            return Err(VerifyError::InfinityPublicKey);
         }
 
-        // Hash message to curve
-        let mut msg_hash = hash_to_curve_g2(msg);
-
         // Points must be affine for pairing
         let mut sig_point = self.point.clone();
         let mut key_point = aggregate_public_key.point.clone();
@@ -245,100 +1338,259 @@ impl AggregateSignature {
         key_point.affine();
         msg_hash.affine();
 
-        let mut generator_g1_negative = amcl_utils::GroupG1::generator();
-        generator_g1_negative.neg(); // already affine
+        let mut generator_g2_negative = GroupG2::generator();
+        generator_g2_negative.neg(); // already affine
 
-        // Faster ate2 evaualtion checks e(S, -G1) * e(H, PK) == 1
-        ate2_evaluation(&sig_point, &generator_g1_negative, &msg_hash, &key_point)
+        // Faster ate2 evaualtion checks e(PK, H) * e(-G2, S) == 1
+        pairing_result(ate2_evaluation(&key_point, &msg_hash, &generator_g2_negative, &sig_point))
     }
 
-    /// Verify Multiple AggregateSignatures
+    /// Verify Multiple MinSigAggregateSignatures
     ///
-    /// Input (AggregateSignature, PublicKey[m], Message(Vec<u8>))[n]
-    /// Checks that each AggregateSignature is valid with a reduced number of pairings.
+    /// Input (MinSigAggregateSignature, MinSigAggregatePublicKey, Message)[n]
+    /// Checks that each MinSigAggregateSignature is valid with a reduced number of pairings.
     /// https://ethresear.ch/t/fast-verification-of-multiple-bls-signatures/5407
     /// Note: Assumes Proof of Possession of public keys.
     pub fn verify_multiple_aggregate_signatures<'a, R, I>(rng: &mut R, signature_sets: I) -> bool
     where
         R: Rng + ?Sized,
-        I: Iterator<Item = (&'a AggregateSignature, &'a AggregatePublicKey, &'a [u8])>,
+        I: Iterator<Item = (&'a MinSigAggregateSignature, &'a MinSigAggregatePublicKey, &'a [u8])>,
     {
-        // Sum of (AggregateSignature[i] * rand[i]) for all AggregateSignatures - S'
-        let mut final_agg_sig = GroupG2::new();
+        Self::try_verify_multiple_aggregate_signatures(rng, signature_sets).is_ok()
+    }
+
+    /// Verify Multiple MinSigAggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures` returning the reason verification failed.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn try_verify_multiple_aggregate_signatures<'a, R, I>(
+        rng: &mut R,
+        signature_sets: I,
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a MinSigAggregateSignature, &'a MinSigAggregatePublicKey, &'a [u8])>,
+    {
+        Self::try_verify_multiple_aggregate_signatures_with_rand_bits(
+            rng,
+            signature_sets,
+            DEFAULT_RAND_BITS,
+        )
+    }
+
+    /// Verify Multiple MinSigAggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures` using random scalars of `rand_bits` bits, an
+    /// invalid batch passes with probability at most 2^-(rand_bits - 1). Returns false if
+    /// `rand_bits` is less than `MIN_RAND_BITS` or greater than `MAX_RAND_BITS`.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn verify_multiple_aggregate_signatures_with_rand_bits<'a, R, I>(
+        rng: &mut R,
+        signature_sets: I,
+        rand_bits: usize,
+    ) -> bool
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a MinSigAggregateSignature, &'a MinSigAggregatePublicKey, &'a [u8])>,
+    {
+        Self::try_verify_multiple_aggregate_signatures_with_rand_bits(
+            rng,
+            signature_sets,
+            rand_bits,
+        )
+        .is_ok()
+    }
 
-        // Stores current value of pairings
-        let mut pairing = pair::initmp();
+    /// Verify Multiple MinSigAggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures_with_rand_bits` returning the reason
+    /// verification failed.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn try_verify_multiple_aggregate_signatures_with_rand_bits<'a, R, I>(
+        rng: &mut R,
+        signature_sets: I,
+        rand_bits: usize,
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a MinSigAggregateSignature, &'a MinSigAggregatePublicKey, &'a [u8])>,
+    {
+        Self::verify_multiple_hashed(rng, signature_sets, hash_to_curve_g1, rand_bits)
+    }
 
-        for (aggregate_signature, aggregate_public_key, message) in signature_sets {
+    /// Verify Multiple MinSigAggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures` where all messages were signed using a custom
+    /// domain separation tag. Returns false if the DST is empty.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn verify_multiple_aggregate_signatures_with_dst<'a, R, I>(
+        rng: &mut R,
+        signature_sets: I,
+        dst: &[u8],
+    ) -> bool
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a MinSigAggregateSignature, &'a MinSigAggregatePublicKey, &'a [u8])>,
+    {
+        Self::try_verify_multiple_aggregate_signatures_with_dst(rng, signature_sets, dst).is_ok()
+    }
+
+    /// Verify Multiple MinSigAggregateSignatures
+    ///
+    /// As `verify_multiple_aggregate_signatures_with_dst` returning the reason verification
+    /// failed.
+    /// Note: Assumes Proof of Possession of public keys.
+    pub fn try_verify_multiple_aggregate_signatures_with_dst<'a, R, I>(
+        rng: &mut R,
+        signature_sets: I,
+        dst: &[u8],
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a MinSigAggregateSignature, &'a MinSigAggregatePublicKey, &'a [u8])>,
+    {
+        let dst = validate_dst(dst).map_err(|_| VerifyError::InvalidDst)?;
+
+        Self::verify_multiple_hashed(
+            rng,
+            signature_sets,
+            |msg| hash_to_curve_g1_with_dst(msg, dst),
+            DEFAULT_RAND_BITS,
+        )
+    }
+
+    // Verify Multiple MinSigAggregateSignatures where messages are hashed to curve by
+    // `hash_message`.
+    //
+    // Every signature set is checked before any message is hashed so that an invalid set costs
+    // no hashing or pairings wherever it is in the batch.
+    fn verify_multiple_hashed<'a, R, I, H>(
+        rng: &mut R,
+        signature_sets: I,
+        hash_message: H,
+        rand_bits: usize,
+    ) -> Result<(), VerifyError>
+    where
+        R: Rng + ?Sized,
+        I: Iterator<Item = (&'a MinSigAggregateSignature, &'a MinSigAggregatePublicKey, &'a [u8])>,
+        H: Fn(&[u8]) -> GroupG1,
+    {
+        if !(MIN_RAND_BITS..=MAX_RAND_BITS).contains(&rand_bits) {
+            return Err(VerifyError::InvalidRandBits);
+        }
+
+        let signature_sets: Vec<_> = signature_sets.collect();
+        for (aggregate_signature, aggregate_public_key, _) in signature_sets.iter() {
             // Verify subgroup of each aggregate_signature
-            if !subgroup_check_g2(&aggregate_signature.point) {
-                return false;
+            if !subgroup_check_g1(&aggregate_signature.point) {
+                return Err(VerifyError::SignatureNotInSubgroup);
             }
 
-            // TODO: Consider increasing rand security from 2^63 to 2^128
-            // Create random offset - rand[i]
-            let mut rand = 0;
-            while rand == 0 {
-                // Require: rand > 0
-                let mut rand_bytes = [0u8; 8]; // bytes
-                rng.fill(&mut rand_bytes);
-                rand = i64::from_be_bytes(rand_bytes).abs();
+            // Ensure MinSigAggregatePublicKey is not infinity
+            if aggregate_public_key.point.is_infinity() {
+                return Err(VerifyError::InfinityPublicKey);
             }
-            let rand = Big::new_int(rand as isize);
+        }
+
+        // AggregateSignature[i] and rand[i] for all AggregateSignatures
+        let mut signature_points = vec![];
+        let mut rands = vec![];
+
+        // Pairs of points whose pairings are multiplied
+        let mut pairs = vec![];
 
-            // Hash message to curve - H(message[i])
-            let mut msg_hash = hash_to_curve_g2(message);
+        for (aggregate_signature, aggregate_public_key, message) in signature_sets {
+            // Create random offset - rand[i]
+            let rand = random_scalar(rng, rand_bits);
 
             // rand[i] * Apk[i]
-            let mut aggregate_public_key = g1mul(&aggregate_public_key.point, &rand);
+            let mut aggregate_public_key = g2mul(&aggregate_public_key.point, &rand);
 
             // Points must be affine before pairings
+            let mut msg_hash = hash_message(message);
             msg_hash.affine();
             aggregate_public_key.affine();
 
-            // Update current pairings: *= e(H(message[i]), rand[i] * Apk[i])
-            pair::another(&mut pairing, &msg_hash, &aggregate_public_key);
+            // Update current pairings: *= e(rand[i] * Apk[i], H(message[i]))
+            pairs.push((aggregate_public_key, msg_hash));
 
-            // S' += rand[i] * AggregateSignature[i]
-            final_agg_sig.add(&g2mul(&aggregate_signature.point, &rand));
+            signature_points.push(aggregate_signature.point.clone());
+            rands.push(rand);
         }
 
-        // Pairing for LHS - e(As', G1)
-        let mut negative_g1 = GroupG1::generator();
-        negative_g1.neg(); // will be affine
+        // Sum of (AggregateSignature[i] * rand[i]) for all AggregateSignatures - S'
+        let mut final_agg_sig = msm_g1(&signature_points, &rands);
+
+        // Pairing for LHS - e(-G2, S')
+        let mut negative_g2 = GroupG2::generator();
+        negative_g2.neg(); // will be affine
         final_agg_sig.affine();
-        pair::another(&mut pairing, &final_agg_sig, &negative_g1);
+        pairs.push((negative_g2, final_agg_sig));
 
         // Complete pairing and verify output is 1.
-        let mut v = pair::miller(&pairing);
-        v = pair::fexp(&v);
-        v.is_unity()
+        pairing_result(multi_pairing_is_unity(&pairs))
     }
 
-    /// Instatiate an AggregateSignature from some bytes.
-    pub fn from_bytes(bytes: &[u8]) -> Result<AggregateSignature, AmclError> {
-        let point = decompress_g2(bytes)?;
+    /// Instatiate a MinSigAggregateSignature from some bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<MinSigAggregateSignature, Error> {
+        let point = decompress_g1(bytes)?;
         Ok(Self { point })
     }
 
-    /// Export (serialize) the AggregateSignature to bytes.
-    pub fn as_bytes(&self) -> [u8; G2_BYTES] {
-        compress_g2(&self.point)
+    /// Export (serialize) the MinSigAggregateSignature to bytes.
+    pub fn as_bytes(&self) -> [u8; G1_BYTES] {
+        compress_g1(&self.point)
     }
 }
 
-impl Default for AggregateSignature {
+impl Default for MinSigAggregateSignature {
     fn default() -> Self {
         Self::new()
     }
 }
 
+// Generate a random non-zero scalar of `bits` bits for batch verification.
+fn random_scalar<R: Rng + ?Sized>(rng: &mut R, bits: usize) -> Big {
+    let start = MODBYTES - bits.div_ceil(8);
+    let mut rand_bytes = [0u8; MODBYTES];
+    let mut rand = Big::new();
+    while rand.is_zilch() {
+        // Require: rand > 0
+        rng.fill(&mut rand_bytes[start..]);
+        rand_bytes[start] &= 0xff >> (8 * (MODBYTES - start) - bits);
+        rand = Big::from_bytes(&rand_bytes);
+    }
+    rand
+}
+
+// Require the same number of messages as PublicKeys and at least one PublicKey.
+fn check_lengths(messages: usize, public_keys: usize) -> Result<(), VerifyError> {
+    if messages != public_keys {
+        return Err(VerifyError::LengthMismatch { messages, public_keys });
+    }
+    if public_keys == 0 {
+        return Err(VerifyError::EmptyInput);
+    }
+    Ok(())
+}
+
+// Map the result of a pairing check.
+fn pairing_result(is_valid: bool) -> Result<(), VerifyError> {
+    if is_valid {
+        Ok(())
+    } else {
+        Err(VerifyError::PairingMismatch)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate hex;
     extern crate rand;
 
-    use super::super::keys::{Keypair, SecretKey};
+    use super::super::amcl_utils::non_subgroup_g2;
+    use super::super::ciphersuite::BasicScheme;
+    use super::super::keys::{Keypair, PreparedPublicKey, SecretKey};
     use super::*;
 
     #[test]
@@ -928,6 +2180,693 @@ mod tests {
         assert!(!aggregate_signature.aggregate_verify(&[&msg, &msg], &[&key_pair.pk]));
     }
 
+    #[test]
+    fn test_aggregate_verify_with_dst() {
+        let mut rng = &mut rand::thread_rng();
+        let n = 10; // Number of signatures
+        let dst = b"MY_APPLICATION_BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+        let msgs: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; 32]).collect();
+        let mut public_keys: Vec<PublicKey> = vec![];
+        let mut aggregate_signature = AggregateSignature::new();
+
+        // Create keys and sign messages
+        for msg in msgs.iter() {
+            let key_pair = Keypair::random(&mut rng);
+            let signature = Signature::new_with_dst(msg, &key_pair.sk, dst).unwrap();
+
+            public_keys.push(key_pair.pk);
+            aggregate_signature.add(&signature);
+        }
+
+        // Convert to references
+        let msgs_refs: Vec<&[u8]> = msgs.iter().map(|x| x.as_slice()).collect();
+        let public_keys_refs: Vec<&PublicKey> = public_keys.iter().collect();
+
+        assert!(aggregate_signature.aggregate_verify_with_dst(&msgs_refs, &public_keys_refs, dst));
+
+        // Verification should be false for the default or an empty DST
+        assert!(!aggregate_signature.aggregate_verify(&msgs_refs, &public_keys_refs));
+        assert!(!aggregate_signature.aggregate_verify_with_dst(&msgs_refs, &public_keys_refs, &[]));
+    }
+
+    #[test]
+    fn test_fast_aggregate_verify_with_dst() {
+        let mut rng = &mut rand::thread_rng();
+        let n = 10; // Number of signatures
+        let dst = b"MY_APPLICATION_BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+        let msg = vec![1u8; 32];
+        let mut public_keys: Vec<PublicKey> = vec![];
+        let mut aggregate_signature = AggregateSignature::new();
+
+        for _ in 0..n {
+            let key_pair = Keypair::random(&mut rng);
+            let signature = Signature::new_with_dst(&msg, &key_pair.sk, dst).unwrap();
+
+            public_keys.push(key_pair.pk);
+            aggregate_signature.add(&signature);
+        }
+
+        let public_keys_refs: Vec<&PublicKey> = public_keys.iter().collect();
+        let aggregate_public_key = AggregatePublicKey::aggregate(&public_keys_refs).unwrap();
+
+        assert!(aggregate_signature.fast_aggregate_verify_with_dst(&msg, &public_keys_refs, dst));
+        assert!(aggregate_signature.fast_aggregate_verify_pre_aggregated_with_dst(
+            &msg,
+            &aggregate_public_key,
+            dst
+        ));
+
+        // Verification should be false for the default or an empty DST
+        assert!(!aggregate_signature.fast_aggregate_verify(&msg, &public_keys_refs));
+        assert!(!aggregate_signature.fast_aggregate_verify_with_dst(&msg, &public_keys_refs, &[]));
+        assert!(!aggregate_signature.fast_aggregate_verify_pre_aggregated_with_dst(
+            &msg,
+            &aggregate_public_key,
+            &[]
+        ));
+    }
+
+    // Zip AggregateSignatures, AggregatePublicKeys and messages into signature sets.
+    fn signature_sets<'a>(
+        aggregate_signatures: &'a [AggregateSignature],
+        aggregate_public_keys: &'a [AggregatePublicKey],
+        msgs: &'a [Vec<u8>],
+    ) -> impl Iterator<Item = (&'a AggregateSignature, &'a AggregatePublicKey, &'a [u8])> {
+        aggregate_signatures
+            .iter()
+            .zip(aggregate_public_keys.iter())
+            .zip(msgs.iter().map(|x| x.as_slice()))
+            .map(|((a, b), c)| (a, b, c))
+    }
+
+    // An AggregateSignature outside the G2 subgroup.
+    fn non_subgroup_signature() -> AggregateSignature {
+        AggregateSignature { point: non_subgroup_g2() }
+    }
+
+    #[test]
+    fn test_try_aggregate_verify() {
+        let mut rng = &mut rand::thread_rng();
+        let msgs: Vec<Vec<u8>> = (0..3).map(|i| vec![i as u8; 32]).collect();
+        let keypairs: Vec<Keypair> = (0..3).map(|_| Keypair::random(&mut rng)).collect();
+        let mut aggregate_signature = AggregateSignature::new();
+        for (msg, keypair) in msgs.iter().zip(keypairs.iter()) {
+            aggregate_signature.add(&Signature::new(msg, &keypair.sk));
+        }
+
+        let msgs_refs: Vec<&[u8]> = msgs.iter().map(|x| x.as_slice()).collect();
+        let public_keys_refs: Vec<&PublicKey> = keypairs.iter().map(|x| &x.pk).collect();
+        assert_eq!(aggregate_signature.try_aggregate_verify(&msgs_refs, &public_keys_refs), Ok(()));
+
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify(&msgs_refs[..2], &public_keys_refs),
+            Err(VerifyError::LengthMismatch { messages: 2, public_keys: 3 })
+        );
+        let public_keys: &[&PublicKey] = &[];
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify(&[], public_keys),
+            Err(VerifyError::EmptyInput)
+        );
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify_with_ciphersuite::<BasicScheme>(
+                &[&msgs[0], &msgs[0]],
+                &public_keys_refs[..2]
+            ),
+            Err(VerifyError::DuplicateMessages)
+        );
+        assert_eq!(
+            non_subgroup_signature().try_aggregate_verify(&msgs_refs, &public_keys_refs),
+            Err(VerifyError::SignatureNotInSubgroup)
+        );
+        assert_eq!(
+            AggregateSignature::new().try_aggregate_verify(&msgs_refs, &public_keys_refs),
+            Err(VerifyError::PairingMismatch)
+        );
+    }
+
+    #[test]
+    fn test_try_fast_aggregate_verify() {
+        let mut rng = &mut rand::thread_rng();
+        let msg = vec![1u8; 32];
+        let keypair = Keypair::random(&mut rng);
+        let aggregate_signature =
+            AggregateSignature::from_signature(&Signature::new(&msg, &keypair.sk));
+
+        assert_eq!(aggregate_signature.try_fast_aggregate_verify(&msg, &[&keypair.pk]), Ok(()));
+
+        let public_keys: &[&PublicKey] = &[];
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify(&msg, public_keys),
+            Err(VerifyError::EmptyInput)
+        );
+        let mut negative_public_key = keypair.pk.clone();
+        negative_public_key.point.neg();
+        assert_eq!(
+            aggregate_signature
+                .try_fast_aggregate_verify(&msg, &[&keypair.pk, &negative_public_key]),
+            Err(VerifyError::InfinityPublicKey)
+        );
+        assert_eq!(
+            non_subgroup_signature().try_fast_aggregate_verify(&msg, &[&keypair.pk]),
+            Err(VerifyError::SignatureNotInSubgroup)
+        );
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify(&[2u8; 32], &[&keypair.pk]),
+            Err(VerifyError::PairingMismatch)
+        );
+    }
+
+    #[test]
+    fn test_try_verify_multiple_signatures() {
+        let mut rng = &mut rand::thread_rng();
+        let msgs: Vec<Vec<u8>> = (0..3).map(|i| vec![i as u8; 32]).collect();
+        let mut aggregate_public_keys: Vec<AggregatePublicKey> = vec![];
+        let mut aggregate_signatures: Vec<AggregateSignature> = vec![];
+
+        for msg in msgs.iter() {
+            let key_pair = Keypair::random(&mut rng);
+            let signature = Signature::new(msg, &key_pair.sk);
+            aggregate_public_keys.push(AggregatePublicKey::from_public_key(&key_pair.pk));
+            aggregate_signatures.push(AggregateSignature::from_signature(&signature));
+        }
+
+        assert_eq!(
+            AggregateSignature::try_verify_multiple_aggregate_signatures(
+                &mut rng,
+                signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs)
+            ),
+            Ok(())
+        );
+
+        // An empty batch is valid
+        assert_eq!(
+            AggregateSignature::try_verify_multiple_aggregate_signatures(
+                &mut rng,
+                signature_sets(&[], &aggregate_public_keys, &msgs)
+            ),
+            Ok(())
+        );
+        assert!(AggregateSignature::verify_multiple_aggregate_signatures(
+            &mut rng,
+            signature_sets(&[], &aggregate_public_keys, &msgs)
+        ));
+        assert_eq!(
+            AggregateSignature::try_verify_multiple_aggregate_signatures_with_rand_bits(
+                &mut rng,
+                signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs),
+                0
+            ),
+            Err(VerifyError::InvalidRandBits)
+        );
+
+        aggregate_signatures.swap(0, 1);
+        assert_eq!(
+            AggregateSignature::try_verify_multiple_aggregate_signatures(
+                &mut rng,
+                signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs)
+            ),
+            Err(VerifyError::PairingMismatch)
+        );
+
+        // The subgroup check takes precedence over the pairing
+        aggregate_signatures[2] = non_subgroup_signature();
+        assert_eq!(
+            AggregateSignature::try_verify_multiple_aggregate_signatures(
+                &mut rng,
+                signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs)
+            ),
+            Err(VerifyError::SignatureNotInSubgroup)
+        );
+    }
+
+    #[test]
+    fn test_try_verify_with_dst_and_prehashed() {
+        let mut rng = &mut rand::thread_rng();
+        let dst = b"MY_APPLICATION_BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+        let msg = vec![1u8; 32];
+        let hashed_message = HashedMessage::new(&msg);
+        let keypair = Keypair::random(&mut rng);
+        let aggregate_public_key = AggregatePublicKey::from_public_key(&keypair.pk);
+        let public_keys: &[&PublicKey] = &[];
+
+        // AggregateVerify
+        let aggregate_signature = AggregateSignature::from_signature(
+            &Signature::new_with_dst(&msg, &keypair.sk, dst).unwrap(),
+        );
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify_with_dst(&[&msg], &[&keypair.pk], dst),
+            Ok(())
+        );
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify_with_dst(&[&msg], &[&keypair.pk], &[]),
+            Err(VerifyError::InvalidDst)
+        );
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify_with_dst(&[&msg, &msg], &[&keypair.pk], dst),
+            Err(VerifyError::LengthMismatch { messages: 2, public_keys: 1 })
+        );
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify_with_dst(&[&msg], &[&keypair.pk], b"OTHER"),
+            Err(VerifyError::PairingMismatch)
+        );
+
+        // FastAggregateVerify
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_with_dst(&msg, &[&keypair.pk], dst),
+            Ok(())
+        );
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_with_dst(&msg, public_keys, dst),
+            Err(VerifyError::EmptyInput)
+        );
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_pre_aggregated_with_dst(
+                &msg,
+                &aggregate_public_key,
+                &[]
+            ),
+            Err(VerifyError::InvalidDst)
+        );
+        assert_eq!(
+            non_subgroup_signature().try_fast_aggregate_verify_pre_aggregated_with_dst(
+                &msg,
+                &aggregate_public_key,
+                dst
+            ),
+            Err(VerifyError::SignatureNotInSubgroup)
+        );
+
+        // Prehashed
+        let aggregate_signature = AggregateSignature::from_signature(&Signature::new_prehashed(
+            &hashed_message,
+            &keypair.sk,
+        ));
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify_prehashed(&[&hashed_message], &[&keypair.pk]),
+            Ok(())
+        );
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify_prehashed(&[], public_keys),
+            Err(VerifyError::EmptyInput)
+        );
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_prehashed(&hashed_message, public_keys),
+            Err(VerifyError::EmptyInput)
+        );
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_pre_aggregated_prehashed(
+                &HashedMessage::new(&[2u8; 32]),
+                &aggregate_public_key
+            ),
+            Err(VerifyError::PairingMismatch)
+        );
+
+        // Batch verification
+        assert_eq!(
+            AggregateSignature::try_verify_multiple_aggregate_signatures_prehashed(
+                &mut rng,
+                vec![(&aggregate_signature, &aggregate_public_key, &hashed_message)].into_iter()
+            ),
+            Ok(())
+        );
+        let invalid_signature = non_subgroup_signature();
+        assert_eq!(
+            AggregateSignature::try_verify_multiple_aggregate_signatures_with_dst(
+                &mut rng,
+                vec![(&invalid_signature, &aggregate_public_key, msg.as_slice())].into_iter(),
+                dst
+            ),
+            Err(VerifyError::SignatureNotInSubgroup)
+        );
+        assert_eq!(
+            AggregateSignature::try_verify_multiple_aggregate_signatures_with_dst(
+                &mut rng,
+                vec![(&aggregate_signature, &aggregate_public_key, msg.as_slice())].into_iter(),
+                &[]
+            ),
+            Err(VerifyError::InvalidDst)
+        );
+    }
+
+    #[test]
+    fn test_verify_multiple_signatures_with_dst() {
+        let mut rng = &mut rand::thread_rng();
+        let n = 5; // Signatures
+        let dst = b"MY_APPLICATION_BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+        let msgs: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; 32]).collect();
+        let mut aggregate_public_keys: Vec<AggregatePublicKey> = vec![];
+        let mut aggregate_signatures: Vec<AggregateSignature> = vec![];
+
+        for msg in msgs.iter() {
+            let key_pair = Keypair::random(&mut rng);
+            let signature = Signature::new_with_dst(msg, &key_pair.sk, dst).unwrap();
+            aggregate_public_keys.push(AggregatePublicKey::from_public_key(&key_pair.pk));
+            aggregate_signatures.push(AggregateSignature::from_signature(&signature));
+        }
+
+        let signature_sets = || {
+            aggregate_signatures
+                .iter()
+                .zip(aggregate_public_keys.iter())
+                .zip(msgs.iter().map(|x| x.as_slice()))
+                .map(|((a, b), c)| (a, b, c))
+        };
+
+        assert!(AggregateSignature::verify_multiple_aggregate_signatures_with_dst(
+            &mut rng,
+            signature_sets(),
+            dst
+        ));
+
+        // Verification should be false for the default or an empty DST
+        assert!(!AggregateSignature::verify_multiple_aggregate_signatures(
+            &mut rng,
+            signature_sets()
+        ));
+        assert!(!AggregateSignature::verify_multiple_aggregate_signatures_with_dst(
+            &mut rng,
+            signature_sets(),
+            &[]
+        ));
+    }
+
+    #[test]
+    fn test_verify_multiple_signatures_with_rand_bits() {
+        let mut rng = &mut rand::thread_rng();
+        let n = 5; // Signatures
+        let msgs: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; 32]).collect();
+        let mut aggregate_public_keys: Vec<AggregatePublicKey> = vec![];
+        let mut aggregate_signatures: Vec<AggregateSignature> = vec![];
+
+        for msg in msgs.iter() {
+            let key_pair = Keypair::random(&mut rng);
+            let signature = Signature::new(msg, &key_pair.sk);
+            aggregate_public_keys.push(AggregatePublicKey::from_public_key(&key_pair.pk));
+            aggregate_signatures.push(AggregateSignature::from_signature(&signature));
+        }
+
+        for rand_bits in &[MIN_RAND_BITS, 128, MAX_RAND_BITS] {
+            assert!(AggregateSignature::verify_multiple_aggregate_signatures_with_rand_bits(
+                &mut rng,
+                signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs),
+                *rand_bits
+            ));
+        }
+
+        // Invalid number of bits
+        for rand_bits in &[0, 1, MIN_RAND_BITS - 1, MAX_RAND_BITS + 1] {
+            assert!(!AggregateSignature::verify_multiple_aggregate_signatures_with_rand_bits(
+                &mut rng,
+                signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs),
+                *rand_bits
+            ));
+        }
+
+        // Swap two signatures so that both sets are invalid
+        aggregate_signatures.swap(0, 1);
+        assert!(!AggregateSignature::verify_multiple_aggregate_signatures_with_rand_bits(
+            &mut rng,
+            signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs),
+            128
+        ));
+    }
+
+    #[test]
+    fn test_find_invalid_signature_sets() {
+        let mut rng = &mut rand::thread_rng();
+        let n = 10; // Signatures
+        let msgs: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; 32]).collect();
+        let mut aggregate_public_keys: Vec<AggregatePublicKey> = vec![];
+        let mut aggregate_signatures: Vec<AggregateSignature> = vec![];
+
+        for msg in msgs.iter() {
+            let key_pair = Keypair::random(&mut rng);
+            let signature = Signature::new(msg, &key_pair.sk);
+            aggregate_public_keys.push(AggregatePublicKey::from_public_key(&key_pair.pk));
+            aggregate_signatures.push(AggregateSignature::from_signature(&signature));
+        }
+
+        // All valid
+        let invalid = AggregateSignature::find_invalid_signature_sets(
+            &mut rng,
+            signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs),
+        );
+        assert!(invalid.is_empty());
+
+        // Swap two signatures so that both sets are invalid
+        aggregate_signatures.swap(2, 7);
+        let invalid = AggregateSignature::find_invalid_signature_sets(
+            &mut rng,
+            signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs),
+        );
+        assert_eq!(invalid, vec![2, 7]);
+
+        // Adjacent invalid sets and the last set
+        aggregate_signatures.swap(2, 7);
+        aggregate_signatures.swap(4, 5);
+        aggregate_signatures[9] = AggregateSignature::new();
+        let invalid = AggregateSignature::find_invalid_signature_sets(
+            &mut rng,
+            signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs),
+        );
+        assert_eq!(invalid, vec![4, 5, 9]);
+
+        // All invalid
+        let aggregate_signatures = vec![AggregateSignature::new(); n];
+        let invalid = AggregateSignature::find_invalid_signature_sets(
+            &mut rng,
+            signature_sets(&aggregate_signatures, &aggregate_public_keys, &msgs),
+        );
+        assert_eq!(invalid, (0..n).collect::<Vec<usize>>());
+
+        // Empty
+        let invalid = AggregateSignature::find_invalid_signature_sets(
+            &mut rng,
+            signature_sets(&[], &aggregate_public_keys, &msgs),
+        );
+        assert!(invalid.is_empty());
+    }
+
+    #[test]
+    fn test_prepared_public_keys() {
+        let mut rng = &mut rand::thread_rng();
+        let n = 5; // Signatures
+        let msgs: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; 32]).collect();
+        let msgs_refs: Vec<&[u8]> = msgs.iter().map(|x| x.as_slice()).collect();
+        let keypairs: Vec<Keypair> = (0..n).map(|_| Keypair::random(&mut rng)).collect();
+        let prepared: Vec<PreparedPublicKey> =
+            keypairs.iter().map(|kp| PreparedPublicKey::from(&kp.pk)).collect();
+        let prepared_refs: Vec<&PreparedPublicKey> = prepared.iter().collect();
+
+        // AggregateVerify of distinct messages
+        let signatures: Vec<Signature> =
+            keypairs.iter().zip(msgs.iter()).map(|(kp, msg)| Signature::new(msg, &kp.sk)).collect();
+        let aggregate_signature =
+            AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>());
+        assert!(aggregate_signature.aggregate_verify_prepared(&msgs_refs, &prepared_refs));
+        assert!(
+            !aggregate_signature.aggregate_verify_prepared(&msgs_refs[1..], &prepared_refs[1..])
+        );
+
+        // FastAggregateVerify of a single message
+        let msg = &msgs[0];
+        let mut aggregate_signature = AggregateSignature::new();
+        for kp in keypairs.iter() {
+            aggregate_signature.add(&Signature::new(msg, &kp.sk));
+        }
+        let public_keys: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let aggregate_public_key = AggregatePublicKey::aggregate(&public_keys).unwrap();
+        let prepared_aggregate = PreparedPublicKey::from(&aggregate_public_key);
+        assert_eq!(prepared_aggregate.as_bytes(), aggregate_public_key.compressed_bytes());
+        assert!(aggregate_signature.fast_aggregate_verify_prepared(msg, &prepared_aggregate));
+        assert!(!aggregate_signature.fast_aggregate_verify_prepared(&msgs[1], &prepared_aggregate));
+
+        // Batch verification
+        let aggregate_signatures: Vec<AggregateSignature> =
+            signatures.iter().map(AggregateSignature::from_signature).collect();
+        let signature_sets = aggregate_signatures
+            .iter()
+            .zip(prepared.iter())
+            .zip(msgs_refs.iter().cloned())
+            .map(|((a, b), c)| (a, b, c));
+        assert!(AggregateSignature::verify_multiple_aggregate_signatures_prepared(
+            &mut rng,
+            signature_sets
+        ));
+
+        let signature_sets = aggregate_signatures
+            .iter()
+            .rev()
+            .zip(prepared.iter())
+            .zip(msgs_refs.iter().cloned())
+            .map(|((a, b), c)| (a, b, c));
+        assert!(!AggregateSignature::verify_multiple_aggregate_signatures_prepared(
+            &mut rng,
+            signature_sets
+        ));
+
+        // Custom DST and prehashed variants
+        let dst = b"BLS_SIG_PREPARED_TEST_DST";
+        let signature = Signature::new_with_dst(msg, &keypairs[0].sk, dst).unwrap();
+        assert!(signature.verify_with_dst(msg, &prepared[0], dst));
+        assert!(!signature.verify_with_dst(msg, &prepared[1], dst));
+        let aggregate_signature = AggregateSignature::from_signature(&signature);
+        assert!(aggregate_signature.aggregate_verify_with_dst(&[msg], &[&prepared[0]], dst));
+        assert!(AggregateSignature::verify_multiple_aggregate_signatures_with_dst(
+            &mut rng,
+            vec![(&aggregate_signature, &prepared[0], msg.as_slice())].into_iter(),
+            dst
+        ));
+
+        let hashed_message = HashedMessage::new(msg);
+        let signature = Signature::new_prehashed(&hashed_message, &keypairs[0].sk);
+        assert!(signature.verify_prehashed(&hashed_message, &prepared[0]));
+        let aggregate_signature = AggregateSignature::from_signature(&signature);
+        assert!(aggregate_signature.aggregate_verify_prehashed(&[&hashed_message], &[&prepared[0]]));
+        assert!(aggregate_signature
+            .fast_aggregate_verify_pre_aggregated_prehashed(&hashed_message, &prepared[0]));
+        assert!(AggregateSignature::verify_multiple_aggregate_signatures_prehashed(
+            &mut rng,
+            vec![(&aggregate_signature, &prepared[0], &hashed_message)].into_iter()
+        ));
+    }
+
+    #[test]
+    fn test_infinity_public_key_rejected() {
+        let mut rng = &mut rand::thread_rng();
+        let keypair = Keypair::random(&mut rng);
+        let mut negative_public_key = keypair.pk.clone();
+        negative_public_key.point.neg();
+        let infinity = AggregatePublicKey::aggregate(&[&keypair.pk, &negative_public_key]).unwrap();
+        let prepared_infinity = PreparedPublicKey::from(&infinity);
+
+        // The infinity Signature would otherwise verify any message against an infinity key
+        let signature = Signature { point: GroupG2::new() };
+        let aggregate_signature = AggregateSignature::new();
+
+        assert!(!signature.verify_prepared(b"anything", &prepared_infinity));
+        assert_eq!(
+            signature.try_verify_prepared(b"anything", &prepared_infinity),
+            Err(VerifyError::InfinityPublicKey)
+        );
+        assert!(!aggregate_signature.aggregate_verify_prepared(&[b"m1"], &[&prepared_infinity]));
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify_prepared(&[b"m1"], &[&prepared_infinity]),
+            Err(VerifyError::InfinityPublicKey)
+        );
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_pre_aggregated(b"m1", &infinity),
+            Err(VerifyError::InfinityPublicKey)
+        );
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_prepared(b"m1", &prepared_infinity),
+            Err(VerifyError::InfinityPublicKey)
+        );
+
+        let msg: &[u8] = b"m1";
+        assert_eq!(
+            AggregateSignature::try_verify_multiple_aggregate_signatures(
+                &mut rng,
+                vec![(&aggregate_signature, &infinity, msg)].into_iter()
+            ),
+            Err(VerifyError::InfinityPublicKey)
+        );
+        assert_eq!(
+            AggregateSignature::find_invalid_signature_sets(
+                &mut rng,
+                vec![(&aggregate_signature, &infinity, msg)].into_iter()
+            ),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn test_prehashed_verification() {
+        let mut rng = &mut rand::thread_rng();
+        let n = 5; // Signatures
+        let msgs: Vec<Vec<u8>> = (0..n).map(|i| vec![i as u8; 32]).collect();
+        let hashed_messages: Vec<HashedMessage> =
+            msgs.iter().map(|msg| HashedMessage::new(msg)).collect();
+        let hashed_refs: Vec<&HashedMessage> = hashed_messages.iter().collect();
+        let keypairs: Vec<Keypair> = (0..n).map(|_| Keypair::random(&mut rng)).collect();
+        let public_keys: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+
+        // AggregateVerify of distinct messages
+        let signatures: Vec<Signature> = keypairs
+            .iter()
+            .zip(hashed_messages.iter())
+            .map(|(kp, hashed)| Signature::new_prehashed(hashed, &kp.sk))
+            .collect();
+        let aggregate_signature =
+            AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>());
+        assert!(aggregate_signature.aggregate_verify_prehashed(&hashed_refs, &public_keys));
+        assert!(!aggregate_signature.aggregate_verify_prehashed(&hashed_refs[1..], &public_keys));
+        assert!(
+            !aggregate_signature.aggregate_verify_prehashed(&hashed_refs[1..], &public_keys[1..])
+        );
+
+        // FastAggregateVerify of a single message
+        let hashed_message = &hashed_messages[0];
+        let mut aggregate_signature = AggregateSignature::new();
+        for kp in keypairs.iter() {
+            aggregate_signature.add(&Signature::new_prehashed(hashed_message, &kp.sk));
+        }
+        assert!(aggregate_signature.fast_aggregate_verify_prehashed(hashed_message, &public_keys));
+        assert!(aggregate_signature.fast_aggregate_verify(&msgs[0], &public_keys));
+        assert!(
+            !aggregate_signature.fast_aggregate_verify_prehashed(hashed_message, &public_keys[1..])
+        );
+        assert!(
+            !aggregate_signature.fast_aggregate_verify_prehashed(hashed_message, &public_keys[..0])
+        );
+
+        let aggregate_public_key = AggregatePublicKey::aggregate(&public_keys).unwrap();
+        assert!(aggregate_signature
+            .fast_aggregate_verify_pre_aggregated_prehashed(hashed_message, &aggregate_public_key));
+        assert!(!aggregate_signature.fast_aggregate_verify_pre_aggregated_prehashed(
+            &hashed_messages[1],
+            &aggregate_public_key
+        ));
+
+        // Batch verification
+        let aggregate_signatures: Vec<AggregateSignature> =
+            signatures.iter().map(AggregateSignature::from_signature).collect();
+        let aggregate_public_keys: Vec<AggregatePublicKey> =
+            keypairs.iter().map(|kp| AggregatePublicKey::from_public_key(&kp.pk)).collect();
+        let signature_sets = aggregate_signatures
+            .iter()
+            .zip(aggregate_public_keys.iter())
+            .zip(hashed_messages.iter())
+            .map(|((a, b), c)| (a, b, c));
+        assert!(AggregateSignature::verify_multiple_aggregate_signatures_prehashed(
+            &mut rng,
+            signature_sets
+        ));
+
+        let signature_sets = aggregate_signatures
+            .iter()
+            .zip(aggregate_public_keys.iter())
+            .zip(hashed_messages.iter().rev())
+            .map(|((a, b), c)| (a, b, c));
+        assert!(!AggregateSignature::verify_multiple_aggregate_signatures_prehashed(
+            &mut rng,
+            signature_sets
+        ));
+    }
+
+    #[test]
+    fn test_random_scalar() {
+        let mut rng = rand::thread_rng();
+        for bits in &[1, 7, 8, 9, 63, 64, 128, MAX_RAND_BITS] {
+            for _ in 0..16 {
+                let rand = random_scalar(&mut rng, *bits);
+                assert!(!rand.is_zilch());
+                assert!(rand.nbits() <= *bits);
+            }
+        }
+        assert_eq!(Big::comp(&random_scalar(&mut rng, 1), &Big::new_int(1)), 0);
+    }
+
     #[test]
     fn test_from_public_key() {
         let multiplier = Big::new_int(5);
@@ -950,6 +2889,258 @@ mod tests {
         assert_eq!(signature.point, aggregate_signature.point);
     }
 
+    // Generate n MinSigPublicKeys and their MinSigSignatures over msgs[i].
+    fn min_sig_signatures(msgs: &[&[u8]]) -> (Vec<MinSigPublicKey>, Vec<MinSigSignature>) {
+        let mut rng = rand::thread_rng();
+        let mut public_keys = vec![];
+        let mut signatures = vec![];
+        for msg in msgs {
+            let sk = SecretKey::random(&mut rng);
+            public_keys.push(MinSigPublicKey::from_secret_key(&sk));
+            signatures.push(MinSigSignature::new(msg, &sk));
+        }
+        (public_keys, signatures)
+    }
+
+    #[test]
+    fn test_min_sig_aggregate_verify() {
+        let msgs: Vec<&[u8]> = vec![b"zero", b"one", b"two", b"three"];
+        let (public_keys, signatures) = min_sig_signatures(&msgs);
+        let public_keys_refs: Vec<&MinSigPublicKey> = public_keys.iter().collect();
+        let signatures_refs: Vec<&MinSigSignature> = signatures.iter().collect();
+
+        let aggregate_signature = MinSigAggregateSignature::aggregate(&signatures_refs);
+        assert!(aggregate_signature.aggregate_verify(&msgs, &public_keys_refs));
+
+        // Serialize and de-serialize the aggregate signature
+        let aggregate_signature =
+            MinSigAggregateSignature::from_bytes(&aggregate_signature.as_bytes()).unwrap();
+        assert!(aggregate_signature.aggregate_verify(&msgs, &public_keys_refs));
+
+        // Wrong messages, missing signatures and mismatched lengths should fail
+        let wrong_msgs: Vec<&[u8]> = vec![b"zero", b"one", b"two", b"four"];
+        assert!(!aggregate_signature.aggregate_verify(&wrong_msgs, &public_keys_refs));
+        let partial_signature = MinSigAggregateSignature::aggregate(&signatures_refs[1..]);
+        assert!(!partial_signature.aggregate_verify(&msgs, &public_keys_refs));
+        assert!(!aggregate_signature.aggregate_verify(&msgs[1..], &public_keys_refs));
+        assert!(!MinSigAggregateSignature::new().aggregate_verify(&[], &[]));
+    }
+
+    #[test]
+    fn test_min_sig_fast_aggregate_verify() {
+        let msg = vec![1u8; 32];
+        let msgs: Vec<&[u8]> = vec![&msg; 5];
+        let (public_keys, signatures) = min_sig_signatures(&msgs);
+        let public_keys_refs: Vec<&MinSigPublicKey> = public_keys.iter().collect();
+
+        let mut aggregate_signature = MinSigAggregateSignature::new();
+        let mut aggregate_public_key = MinSigAggregatePublicKey::from_public_key(&public_keys[0]);
+        aggregate_signature.add(&signatures[0]);
+        for (pk, sig) in public_keys.iter().zip(signatures.iter()).skip(1) {
+            aggregate_public_key.add(pk);
+            aggregate_signature.add(sig);
+        }
+
+        assert!(aggregate_signature.fast_aggregate_verify(&msg, &public_keys_refs));
+        assert!(
+            aggregate_signature.fast_aggregate_verify_pre_aggregated(&msg, &aggregate_public_key)
+        );
+        assert!(!aggregate_signature.fast_aggregate_verify(&[2u8; 32], &public_keys_refs));
+        assert!(!aggregate_signature.fast_aggregate_verify(&msg, &public_keys_refs[1..]));
+        assert!(!aggregate_signature.fast_aggregate_verify(&msg, &[]));
+    }
+
+    #[test]
+    fn test_min_sig_verify_multiple_signatures() {
+        let mut rng = rand::thread_rng();
+        let msgs: Vec<&[u8]> = vec![b"zero", b"one", b"two", b"three"];
+        let (public_keys, signatures) = min_sig_signatures(&msgs);
+        let aggregate_public_keys: Vec<MinSigAggregatePublicKey> =
+            public_keys.iter().map(MinSigAggregatePublicKey::from_public_key).collect();
+        let mut aggregate_signatures: Vec<MinSigAggregateSignature> =
+            signatures.iter().map(MinSigAggregateSignature::from_signature).collect();
+
+        let valid = MinSigAggregateSignature::verify_multiple_aggregate_signatures(
+            &mut rng,
+            aggregate_signatures
+                .iter()
+                .zip(aggregate_public_keys.iter())
+                .zip(msgs.iter().cloned())
+                .map(|((a, b), c)| (a, b, c)),
+        );
+        assert!(valid);
+
+        // Swap two signatures so that both sets are invalid
+        aggregate_signatures.swap(0, 1);
+        let valid = MinSigAggregateSignature::verify_multiple_aggregate_signatures(
+            &mut rng,
+            aggregate_signatures
+                .iter()
+                .zip(aggregate_public_keys.iter())
+                .zip(msgs.iter().cloned())
+                .map(|((a, b), c)| (a, b, c)),
+        );
+        assert!(!valid);
+
+        let valid = MinSigAggregateSignature::verify_multiple_aggregate_signatures_with_rand_bits(
+            &mut rng,
+            aggregate_signatures
+                .iter()
+                .zip(aggregate_public_keys.iter())
+                .zip(msgs.iter().cloned())
+                .map(|((a, b), c)| (a, b, c)),
+            128,
+        );
+        assert!(!valid);
+
+        // Swap back so the signatures are valid again
+        aggregate_signatures.swap(0, 1);
+        let valid = MinSigAggregateSignature::verify_multiple_aggregate_signatures_with_rand_bits(
+            &mut rng,
+            aggregate_signatures
+                .iter()
+                .zip(aggregate_public_keys.iter())
+                .zip(msgs.iter().cloned())
+                .map(|((a, b), c)| (a, b, c)),
+            128,
+        );
+        assert!(valid);
+    }
+
+    #[test]
+    fn test_min_sig_verify_with_dst() {
+        let mut rng = rand::thread_rng();
+        let dst = b"MY_APPLICATION_BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+        let msgs: Vec<&[u8]> = vec![b"zero", b"one", b"two"];
+        let secret_keys: Vec<SecretKey> = (0..3).map(|_| SecretKey::random(&mut rng)).collect();
+        let public_keys: Vec<MinSigPublicKey> =
+            secret_keys.iter().map(MinSigPublicKey::from_secret_key).collect();
+        let public_keys_refs: Vec<&MinSigPublicKey> = public_keys.iter().collect();
+
+        // AggregateVerify of distinct messages
+        let signatures: Vec<MinSigSignature> = secret_keys
+            .iter()
+            .zip(msgs.iter())
+            .map(|(sk, msg)| MinSigSignature::new_with_dst(msg, sk, dst).unwrap())
+            .collect();
+        let aggregate_signature =
+            MinSigAggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>());
+        assert!(aggregate_signature.aggregate_verify_with_dst(&msgs, &public_keys_refs, dst));
+        assert!(!aggregate_signature.aggregate_verify(&msgs, &public_keys_refs));
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify_with_dst(&msgs, &public_keys_refs, &[]),
+            Err(VerifyError::InvalidDst)
+        );
+
+        // Verify Multiple of the individual signatures
+        let aggregate_public_keys: Vec<MinSigAggregatePublicKey> =
+            public_keys.iter().map(MinSigAggregatePublicKey::from_public_key).collect();
+        let aggregate_signatures: Vec<MinSigAggregateSignature> =
+            signatures.iter().map(MinSigAggregateSignature::from_signature).collect();
+        let signature_sets = || {
+            aggregate_signatures
+                .iter()
+                .zip(aggregate_public_keys.iter())
+                .zip(msgs.iter().cloned())
+                .map(|((a, b), c)| (a, b, c))
+        };
+        assert!(MinSigAggregateSignature::verify_multiple_aggregate_signatures_with_dst(
+            &mut rng,
+            signature_sets(),
+            dst
+        ));
+        assert!(!MinSigAggregateSignature::verify_multiple_aggregate_signatures(
+            &mut rng,
+            signature_sets()
+        ));
+        assert_eq!(
+            MinSigAggregateSignature::try_verify_multiple_aggregate_signatures_with_dst(
+                &mut rng,
+                signature_sets(),
+                &[]
+            ),
+            Err(VerifyError::InvalidDst)
+        );
+
+        // FastAggregateVerify of a common message
+        let msg = b"common";
+        let signatures: Vec<MinSigSignature> = secret_keys
+            .iter()
+            .map(|sk| MinSigSignature::new_with_dst(msg, sk, dst).unwrap())
+            .collect();
+        let aggregate_signature =
+            MinSigAggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>());
+        let aggregate_public_key = MinSigAggregatePublicKey::aggregate(&public_keys_refs).unwrap();
+        assert!(aggregate_signature.fast_aggregate_verify_with_dst(msg, &public_keys_refs, dst));
+        assert!(aggregate_signature.fast_aggregate_verify_pre_aggregated_with_dst(
+            msg,
+            &aggregate_public_key,
+            dst
+        ));
+        assert!(!aggregate_signature.fast_aggregate_verify(msg, &public_keys_refs));
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_with_dst(msg, &[], dst),
+            Err(VerifyError::EmptyInput)
+        );
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_pre_aggregated_with_dst(
+                msg,
+                &aggregate_public_key,
+                &[]
+            ),
+            Err(VerifyError::InvalidDst)
+        );
+    }
+
+    #[test]
+    fn test_min_sig_infinity_public_key_rejected() {
+        let mut rng = &mut rand::thread_rng();
+        let sk = SecretKey::random(&mut rng);
+        let public_key = MinSigPublicKey::from_secret_key(&sk);
+        let mut negative_public_key = public_key.clone();
+        negative_public_key.point.neg();
+        let infinity =
+            MinSigAggregatePublicKey::aggregate(&[&public_key, &negative_public_key]).unwrap();
+
+        // MinSigPublicKey::from_bytes_unchecked accepts the point at infinity
+        let mut infinity_bytes = [0u8; G2_BYTES];
+        infinity_bytes[0] = 0xc0;
+        let infinity_public_key = MinSigPublicKey::from_bytes_unchecked(&infinity_bytes).unwrap();
+
+        // The infinity Signature would otherwise verify any message against an infinity key
+        let signature = MinSigSignature { point: GroupG1::new() };
+        let aggregate_signature = MinSigAggregateSignature::new();
+
+        assert!(!signature.verify(b"anything", &infinity_public_key));
+        assert_eq!(
+            signature.try_verify(b"anything", &infinity_public_key),
+            Err(VerifyError::InfinityPublicKey)
+        );
+        assert!(!aggregate_signature.aggregate_verify(&[b"m1"], &[&infinity_public_key]));
+        assert_eq!(
+            aggregate_signature.try_aggregate_verify(&[b"m1"], &[&infinity_public_key]),
+            Err(VerifyError::InfinityPublicKey)
+        );
+        assert_eq!(
+            aggregate_signature.try_fast_aggregate_verify_pre_aggregated(b"m1", &infinity),
+            Err(VerifyError::InfinityPublicKey)
+        );
+
+        let msg: &[u8] = b"m1";
+        assert_eq!(
+            MinSigAggregateSignature::try_verify_multiple_aggregate_signatures(
+                &mut rng,
+                vec![(&aggregate_signature, &infinity, msg)].into_iter()
+            ),
+            Err(VerifyError::InfinityPublicKey)
+        );
+        assert!(!MinSigAggregateSignature::verify_multiple_aggregate_signatures_with_rand_bits(
+            &mut rng,
+            vec![(&aggregate_signature, &infinity, msg)].into_iter(),
+            DEFAULT_RAND_BITS
+        ));
+    }
+
     #[test]
     fn test_readme() {
         // An exact replica of the README.md at the top level.
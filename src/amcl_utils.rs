@@ -2,8 +2,19 @@ extern crate amcl;
 #[cfg(feature = "std")]
 extern crate hex;
 extern crate rand;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+extern crate zeroize;
 
-use BLSCurve::bls381::proof_of_possession::DST_G2;
+#[cfg(feature = "parallel")]
+use self::rayon::prelude::*;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use self::zeroize::Zeroize;
+use super::error::Error;
+use BLSCurve::bls381::proof_of_possession::{DST_G1, DST_G2, DST_POP_G1, DST_POP_G2};
 use BLSCurve::ecp::ECP;
 use BLSCurve::ecp2::ECP2;
 use BLSCurve::pair::{ate2, fexp};
@@ -27,11 +38,60 @@ pub type GroupG2 = ECP2;
 lazy_static! {
     pub static ref GENERATORG1: GroupG1 = GroupG1::generator();
     pub static ref GENERATORG2: GroupG2 = GroupG2::generator();
+    static ref GENERATORG1_TABLE: Vec<Vec<GroupG1>> = fixed_base_table(&GENERATORG1);
+    static ref GENERATORG2_TABLE: Vec<Vec<GroupG2>> = fixed_base_table(&GENERATORG2);
 }
 
+// Bits of the scalar covered by each row of a fixed-base table
+#[cfg(feature = "std")]
+const FIXED_BASE_WINDOW: usize = 4;
+// Rows of a fixed-base table, enough for scalars below 2^256
+#[cfg(feature = "std")]
+const FIXED_BASE_ROWS: usize = 64;
+
+// Flags in the most significant bits of an encoded point
+const COMPRESSION_FLAG: u8 = 0b_1000_0000;
+const INFINITY_FLAG: u8 = 0b_0100_0000;
+const Y_FLAG: u8 = 0b_0010_0000;
+
 // Take given message convert it to GroupG2 point
 pub fn hash_to_curve_g2(msg: &[u8]) -> GroupG2 {
-    utils::hash_to_curve_g2(msg, DST_G2)
+    hash_to_curve_g2_with_dst(msg, DST_G2)
+}
+
+// Take given message and domain separation tag and convert it to GroupG2 point
+pub fn hash_to_curve_g2_with_dst(msg: &[u8], dst: &[u8]) -> GroupG2 {
+    utils::hash_to_curve_g2(msg, dst)
+}
+
+// Take given message convert it to GroupG1 point
+pub fn hash_to_curve_g1(msg: &[u8]) -> GroupG1 {
+    hash_to_curve_g1_with_dst(msg, DST_G1)
+}
+
+// Take given message and domain separation tag and convert it to GroupG1 point
+pub fn hash_to_curve_g1_with_dst(msg: &[u8], dst: &[u8]) -> GroupG1 {
+    utils::hash_to_curve_g1(msg, dst)
+}
+
+// Validate a domain separation tag for hashing to curve.
+// Tags MUST be non-empty, tags longer than 255 bytes are reduced by amcl's expand_message_xmd.
+// See https://tools.ietf.org/html/draft-irtf-cfrg-hash-to-curve-09#section-5.3.3
+pub fn validate_dst(dst: &[u8]) -> Result<&[u8], Error> {
+    if dst.is_empty() {
+        return Err(Error::InvalidDst);
+    }
+    Ok(dst)
+}
+
+// Take a compressed PublicKey and convert it to a GroupG2 point for Proof of Possession
+pub fn hash_public_key_to_g2(public_key: &[u8]) -> GroupG2 {
+    utils::hash_to_curve_g2(public_key, DST_POP_G2)
+}
+
+// Take a compressed MinSigPublicKey and convert it to a GroupG1 point for Proof of Possession
+pub fn hash_public_key_to_g1(public_key: &[u8]) -> GroupG1 {
+    utils::hash_to_curve_g1(public_key, DST_POP_G1)
 }
 
 // Evaluation of e(A, B) * e(C, D) == 1
@@ -41,20 +101,293 @@ pub fn ate2_evaluation(a: &GroupG2, b: &GroupG1, c: &GroupG2, d: &GroupG1) -> bo
     FP12::new_int(1).equals(&pairing)
 }
 
+// Product of the Miller loops e(P[i], Q[i]) of each pair, without the final exponentiation.
+//
+// With the `parallel` feature the pairs are split across threads and the partial Miller loops
+// are multiplied together.
+pub fn multi_miller_loop(pairs: &[(GroupG2, GroupG1)]) -> FP12 {
+    #[cfg(feature = "parallel")]
+    {
+        let chunk_size = pairs.len().div_ceil(rayon::current_num_threads()).max(1);
+        pairs.par_chunks(chunk_size).map(serial_miller_loop).reduce(
+            || FP12::new_int(1),
+            |mut product, partial| {
+                product.mul(&partial);
+                product
+            },
+        )
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        serial_miller_loop(pairs)
+    }
+}
+
+// Accumulate the line functions of each pair then complete a single Miller loop.
+fn serial_miller_loop(pairs: &[(GroupG2, GroupG1)]) -> FP12 {
+    let mut pairing = pair::initmp();
+    for (p, q) in pairs {
+        pair::another(&mut pairing, p, q);
+    }
+    pair::miller(&pairing)
+}
+
+// Evaluation of the product of pairings e(P[i], Q[i]) == 1
+pub fn multi_pairing_is_unity(pairs: &[(GroupG2, GroupG1)]) -> bool {
+    fexp(&multi_miller_loop(pairs)).is_unity()
+}
+
+// Multi-scalar multiplication: the sum of scalars[i] * points[i] in G1.
+//
+// Uses Pippenger's bucket method, panics if the number of points and scalars differ.
+pub fn msm_g1(points: &[GroupG1], scalars: &[Big]) -> GroupG1 {
+    pippenger(points, scalars)
+}
+
+// Multi-scalar multiplication: the sum of scalars[i] * points[i] in G2.
+//
+// Uses Pippenger's bucket method, panics if the number of points and scalars differ.
+pub fn msm_g2(points: &[GroupG2], scalars: &[Big]) -> GroupG2 {
+    pippenger(points, scalars)
+}
+
+// Group operations required by the bucket method and fixed-base tables.
+trait CurvePoint: Clone {
+    fn infinity() -> Self;
+    fn add_point(&mut self, other: &Self);
+    fn double_point(&mut self);
+    #[cfg(feature = "std")]
+    fn cmove_point(&mut self, other: &Self, d: isize);
+}
+
+impl CurvePoint for GroupG1 {
+    fn infinity() -> Self {
+        GroupG1::new()
+    }
+
+    fn add_point(&mut self, other: &Self) {
+        self.add(other);
+    }
+
+    fn double_point(&mut self) {
+        self.dbl();
+    }
+
+    #[cfg(feature = "std")]
+    fn cmove_point(&mut self, other: &Self, d: isize) {
+        self.cmove(other, d);
+    }
+}
+
+impl CurvePoint for GroupG2 {
+    fn infinity() -> Self {
+        GroupG2::new()
+    }
+
+    fn add_point(&mut self, other: &Self) {
+        self.add(other);
+    }
+
+    fn double_point(&mut self) {
+        self.dbl();
+    }
+
+    #[cfg(feature = "std")]
+    fn cmove_point(&mut self, other: &Self, d: isize) {
+        self.cmove(other, d);
+    }
+}
+
+// Pippenger's bucket method.
+//
+// Scalars are split into windows of `c` bits. For each window the points are added to the
+// bucket of their digit, the buckets are summed as sum(digit * bucket[digit]) using running
+// sums and the window results are combined with `c` doublings between each window.
+fn pippenger<P: CurvePoint>(points: &[P], scalars: &[Big]) -> P {
+    assert_eq!(points.len(), scalars.len(), "number of points and scalars must match");
+
+    let scalars: Vec<Big> = scalars
+        .iter()
+        .map(|scalar| {
+            let mut scalar = scalar.clone();
+            scalar.norm();
+            scalar
+        })
+        .collect();
+    let bits = scalars.iter().map(Big::nbits).max().unwrap_or(0);
+
+    let c = window_bits(points.len());
+    let mut buckets = vec![P::infinity(); (1 << c) - 1];
+    let mut result = P::infinity();
+
+    for window in (0..bits.div_ceil(c)).rev() {
+        for _ in 0..c {
+            result.double_point();
+        }
+
+        for bucket in buckets.iter_mut() {
+            *bucket = P::infinity();
+        }
+        for (point, scalar) in points.iter().zip(scalars.iter()) {
+            let digit = window_digit(scalar, window * c, c, bits);
+            if digit > 0 {
+                buckets[digit - 1].add_point(point);
+            }
+        }
+
+        // sum(digit * bucket[digit]) = sum of the running sums from the highest bucket down
+        let mut running_sum = P::infinity();
+        let mut window_sum = P::infinity();
+        for bucket in buckets.iter().rev() {
+            running_sum.add_point(bucket);
+            window_sum.add_point(&running_sum);
+        }
+        result.add_point(&window_sum);
+    }
+
+    result
+}
+
+// Window size in bits, approximately ln(n) + 2 for larger inputs.
+fn window_bits(n: usize) -> usize {
+    if n < 4 {
+        1
+    } else if n < 32 {
+        3
+    } else {
+        (n.ilog2() as usize) * 69 / 100 + 2
+    }
+}
+
+// The `c` bit digit of `scalar` starting at bit `start`, ignoring bits from `bits` upwards.
+fn window_digit(scalar: &Big, start: usize, c: usize, bits: usize) -> usize {
+    (start..(start + c).min(bits))
+        .filter(|i| scalar.bit(*i) == 1)
+        .fold(0, |digit, i| digit | 1 << (i - start))
+}
+
+// Multiply the G1 generator by a (secret) scalar.
+//
+// With `std` this uses a precomputed fixed-base table. The tables are heap allocated on first
+// use and no `const` tables are provided, so `no_std` builds are out of scope and always fall
+// back to `g1mul`.
+pub fn g1_generator_mul(scalar: &Big) -> GroupG1 {
+    #[cfg(feature = "std")]
+    {
+        fixed_base_mul(&GENERATORG1_TABLE, scalar)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        g1mul(&GroupG1::generator(), scalar)
+    }
+}
+
+// Multiply the G2 generator by a (secret) scalar.
+//
+// With `std` this uses a precomputed fixed-base table. The tables are heap allocated on first
+// use and no `const` tables are provided, so `no_std` builds are out of scope and always fall
+// back to `g2mul`.
+pub fn g2_generator_mul(scalar: &Big) -> GroupG2 {
+    #[cfg(feature = "std")]
+    {
+        fixed_base_mul(&GENERATORG2_TABLE, scalar)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        g2mul(&GroupG2::generator(), scalar)
+    }
+}
+
+// Fixed-base table where table[row][digit] = digit * 2^(FIXED_BASE_WINDOW * row) * base.
+#[cfg(feature = "std")]
+fn fixed_base_table<P: CurvePoint>(base: &P) -> Vec<Vec<P>> {
+    let mut table = Vec::with_capacity(FIXED_BASE_ROWS);
+    let mut row_base = base.clone();
+    for _ in 0..FIXED_BASE_ROWS {
+        let mut row = Vec::with_capacity(1 << FIXED_BASE_WINDOW);
+        let mut point = P::infinity();
+        for _ in 0..(1 << FIXED_BASE_WINDOW) {
+            row.push(point.clone());
+            point.add_point(&row_base);
+        }
+        // 2^FIXED_BASE_WINDOW * row_base
+        row_base = point;
+        table.push(row);
+    }
+    table
+}
+
+// Sum of table[row][digit] for each digit of the scalar.
+//
+// Every entry of a row is read and selected with `cmove` so the memory access pattern does not
+// depend on the scalar.
+#[cfg(feature = "std")]
+fn fixed_base_mul<P: CurvePoint>(table: &[Vec<P>], scalar: &Big) -> P {
+    // The generators have order r, reducing the scalar modulo r does not change the product and
+    // ensures no bits lie above the last row.
+    let mut scalar = scalar.clone();
+    scalar.rmod(&Big::new_ints(&CURVE_ORDER));
+
+    let mut result = P::infinity();
+    for (row_index, row) in table.iter().enumerate() {
+        let mut digit = 0;
+        for i in 0..FIXED_BASE_WINDOW {
+            digit |= scalar.bit(row_index * FIXED_BASE_WINDOW + i) << i;
+        }
+
+        let mut selected = P::infinity();
+        for (j, point) in row.iter().enumerate() {
+            // 1 if j == digit else 0
+            let d = ((((j as isize) ^ digit) - 1) >> (isize::BITS - 1)) & 1;
+            selected.cmove_point(point, d);
+        }
+        result.add_point(&selected);
+    }
+
+    scalar.w.zeroize();
+    result
+}
+
 // Take a GroupG1 point (x, y) and compress it to a 384 bit array.
 // See https://github.com/zkcrypto/pairing/blob/master/src/bls12_381/README.md#serialization
 pub fn compress_g1(g1: &GroupG1) -> [u8; G1_BYTES] {
     serialize_g1(g1)
 }
 
+// Check the flags of an encoded point before it is deserialized.
+// The sign flag is only set for compressed points and infinity has no other bits set.
+// See https://github.com/zkcrypto/pairing/blob/master/src/bls12_381/README.md#serialization
+fn check_flags(bytes: &[u8], compressed: bool) -> Result<(), Error> {
+    let flags = bytes[0];
+    if (flags & COMPRESSION_FLAG != 0) != compressed || (!compressed && flags & Y_FLAG != 0) {
+        return Err(Error::InvalidFlags);
+    }
+    if flags & INFINITY_FLAG != 0
+        && (flags & !(COMPRESSION_FLAG | INFINITY_FLAG) != 0 || bytes[1..].iter().any(|b| *b != 0))
+    {
+        return Err(Error::InvalidFlags);
+    }
+    Ok(())
+}
+
 // Take a 384 bit array and convert to GroupG1 point (x, y)
 // See https://github.com/zkcrypto/pairing/blob/master/src/bls12_381/README.md#serialization
-pub fn decompress_g1(g1_bytes: &[u8]) -> Result<GroupG1, AmclError> {
+pub fn decompress_g1(g1_bytes: &[u8]) -> Result<GroupG1, Error> {
     // Ensure it is compressed
     if g1_bytes.len() != G1_BYTES {
-        return Err(AmclError::InvalidG1Size);
+        return Err(Error::InvalidLength { expected: G1_BYTES });
     }
-    deserialize_g1(g1_bytes)
+    check_flags(g1_bytes, true)?;
+    Ok(deserialize_g1(g1_bytes)?)
+}
+
+// Take a 384*2 bit array of the (x, y) coordinates and convert to GroupG1 point
+pub fn deserialize_uncompressed_g1(g1_bytes: &[u8]) -> Result<GroupG1, Error> {
+    if g1_bytes.len() != G1_BYTES * 2 {
+        return Err(Error::InvalidLength { expected: G1_BYTES * 2 });
+    }
+    check_flags(g1_bytes, false)?;
+    Ok(deserialize_g1(g1_bytes)?)
 }
 
 // Take a GroupG2 point (x, y) and compress it to a 384*2 bit array.
@@ -65,17 +398,178 @@ pub fn compress_g2(g2: &GroupG2) -> [u8; G2_BYTES] {
 
 // Take a 384*2 bit array and convert to GroupG2 point (x, y)
 // See https://github.com/zkcrypto/pairing/blob/master/src/bls12_381/README.md#serialization
-pub fn decompress_g2(g2_bytes: &[u8]) -> Result<GroupG2, AmclError> {
+pub fn decompress_g2(g2_bytes: &[u8]) -> Result<GroupG2, Error> {
     // Ensure it is compressed
     if g2_bytes.len() != G2_BYTES {
-        return Err(AmclError::InvalidG2Size);
+        return Err(Error::InvalidLength { expected: G2_BYTES });
     }
-    deserialize_g2(g2_bytes)
+    check_flags(g2_bytes, true)?;
+    Ok(deserialize_g2(g2_bytes)?)
+}
+
+// A point on the curve outside the G2 subgroup, for testing subgroup checks.
+#[cfg(test)]
+pub(crate) fn non_subgroup_g2() -> GroupG2 {
+    let mut bytes = [0u8; G2_BYTES];
+    bytes[0] = 0b_1000_0000;
+    for x in 1.. {
+        bytes[G2_BYTES - 1] = x;
+        if let Ok(point) = decompress_g2(&bytes) {
+            assert!(!subgroup_check_g2(&point));
+            return point;
+        }
+    }
+    unreachable!()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::Rng;
+
+    // Pairs of random multiples of the generators
+    fn random_pairs(n: usize) -> Vec<(GroupG2, GroupG1)> {
+        let mut rng = rand::thread_rng();
+        (0..n)
+            .map(|_| {
+                let a = Big::new_int(rng.gen::<u32>() as isize);
+                let b = Big::new_int(rng.gen::<u32>() as isize);
+                (g2mul(&GroupG2::generator(), &a), g1mul(&GroupG1::generator(), &b))
+            })
+            .collect()
+    }
+
+    fn fp12_bytes(fp12: &FP12) -> Vec<u8> {
+        let mut bytes = vec![0u8; 12 * MODBYTES];
+        fp12.to_bytes(&mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn test_multi_miller_loop_matches_serial() {
+        for n in &[0, 1, 2, 3, 8, 17] {
+            let pairs = random_pairs(*n);
+            let serial = serial_miller_loop(&pairs);
+            let multi = multi_miller_loop(&pairs);
+
+            assert!(serial.equals(&multi));
+            assert_eq!(fp12_bytes(&fexp(&serial)), fp12_bytes(&fexp(&multi)));
+        }
+    }
+
+    #[test]
+    fn test_multi_pairing_is_unity() {
+        // e(a * G2, b * G1) * e(-ab * G2, G1) == 1
+        let a = Big::new_int(7);
+        let b = Big::new_int(11);
+        let mut negative_ab = g2mul(&GroupG2::generator(), &Big::new_int(77));
+        negative_ab.neg();
+
+        let mut pairs = vec![
+            (g2mul(&GroupG2::generator(), &a), g1mul(&GroupG1::generator(), &b)),
+            (negative_ab, GroupG1::generator()),
+        ];
+        assert!(multi_pairing_is_unity(&pairs));
+        assert!(multi_pairing_is_unity(&[]));
+
+        pairs.push(random_pairs(1).pop().unwrap());
+        assert!(!multi_pairing_is_unity(&pairs));
+    }
+
+    // Random scalars modulo the curve order
+    fn random_scalars(n: usize) -> Vec<Big> {
+        let mut rng = rand::thread_rng();
+        (0..n)
+            .map(|_| {
+                let mut bytes = [0u8; MODBYTES];
+                rng.fill(&mut bytes[..]);
+                let mut scalar = Big::from_bytes(&bytes);
+                scalar.rmod(&Big::new_ints(&CURVE_ORDER));
+                scalar
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_msm_matches_naive() {
+        for n in &[0, 1, 2, 3, 4, 31, 32, 70] {
+            let pairs = random_pairs(*n);
+            let g1_points: Vec<GroupG1> = pairs.iter().map(|(_, q)| q.clone()).collect();
+            let g2_points: Vec<GroupG2> = pairs.iter().map(|(p, _)| p.clone()).collect();
+            let scalars = random_scalars(*n);
+
+            let mut expected_g1 = GroupG1::new();
+            let mut expected_g2 = GroupG2::new();
+            for i in 0..*n {
+                expected_g1.add(&g1mul(&g1_points[i], &scalars[i]));
+                expected_g2.add(&g2mul(&g2_points[i], &scalars[i]));
+            }
+
+            assert!(msm_g1(&g1_points, &scalars).equals(&expected_g1));
+            assert!(msm_g2(&g2_points, &scalars).equals(&expected_g2));
+        }
+    }
+
+    #[test]
+    fn test_msm_edge_cases() {
+        let g1 = GroupG1::generator();
+        let g2 = GroupG2::generator();
+
+        // Zero scalars
+        let zeros = vec![Big::new(); 5];
+        assert!(msm_g1(&vec![g1.clone(); 5], &zeros).is_infinity());
+        assert!(msm_g2(&vec![g2.clone(); 5], &zeros).is_infinity());
+
+        // Repeated points land in the same bucket: 40 * 3 = 120
+        let scalars = vec![Big::new_int(3); 40];
+        assert!(msm_g1(&vec![g1.clone(); 40], &scalars).equals(&g1mul(&g1, &Big::new_int(120))));
+        assert!(msm_g2(&vec![g2.clone(); 40], &scalars).equals(&g2mul(&g2, &Big::new_int(120))));
+
+        // Points at infinity
+        let points = vec![GroupG1::new(), g1.clone()];
+        let scalars = vec![Big::new_int(5), Big::new_int(7)];
+        assert!(msm_g1(&points, &scalars).equals(&g1mul(&g1, &Big::new_int(7))));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_msm_length_mismatch() {
+        msm_g1(&[GroupG1::generator()], &[]);
+    }
+
+    #[test]
+    fn test_generator_mul_matches_generic_mul() {
+        let mut r_minus_one = Big::new_ints(&CURVE_ORDER);
+        r_minus_one.dec(1);
+        let mut max_scalar = Big::from_bytes(&[0xff; MODBYTES]);
+        max_scalar.mod2m(256);
+
+        let mut scalars = vec![Big::new(), Big::new_int(1), Big::new_int(16), r_minus_one];
+        scalars.push(max_scalar);
+        // Scalars wider than the fixed-base table
+        scalars.push(Big::from_bytes(&[0xff; MODBYTES]));
+        scalars.extend(random_scalars(10));
+
+        for scalar in scalars.iter() {
+            let g1 = g1_generator_mul(scalar);
+            assert!(g1.equals(&g1mul(&GroupG1::generator(), scalar)));
+
+            let g2 = g2_generator_mul(scalar);
+            assert!(g2.equals(&g2mul(&GroupG2::generator(), scalar)));
+        }
+        assert!(g1_generator_mul(&Big::new()).is_infinity());
+    }
+
+    #[test]
+    fn test_validate_dst() {
+        // Empty DST is invalid
+        assert_eq!(validate_dst(&[]), Err(Error::InvalidDst));
+
+        // Any other DST is used as is
+        assert_eq!(validate_dst(b"A"), Ok(&b"A"[..]));
+        let dst = vec![7u8; 256];
+        assert_eq!(validate_dst(&dst), Ok(&dst[..]));
+    }
 
     #[test]
     fn compression_decompression_g1_round_trip() {
@@ -0,0 +1,250 @@
+extern crate amcl;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap, VecDeque};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, VecDeque};
+
+use super::compressed::PublicKeyBytes;
+use super::error::Error;
+use super::keys::PublicKey;
+
+// A validated PublicKey and its optional validator index.
+#[derive(Clone)]
+struct CachedPublicKey {
+    public_key: PublicKey,
+    index: Option<u64>,
+}
+
+/// A bounded cache of validated PublicKeys keyed by their compressed bytes.
+///
+/// Each miss decompresses and validates the PublicKey with `PublicKey::from_bytes`. When the cache
+/// is full the oldest inserted PublicKey is evicted.
+#[derive(Clone)]
+pub struct PublicKeyCache {
+    capacity: usize,
+    keys: BTreeMap<PublicKeyBytes, CachedPublicKey>,
+    insertion_order: VecDeque<PublicKeyBytes>,
+    hits: u64,
+    misses: u64,
+}
+
+impl PublicKeyCache {
+    /// Instantiate an empty PublicKeyCache holding at most `capacity` PublicKeys, at least one.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            keys: BTreeMap::new(),
+            insertion_order: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Get the PublicKey of some compressed bytes.
+    ///
+    /// On a miss the PublicKey is decompressed, validated and inserted into the cache.
+    pub fn get(&mut self, bytes: &PublicKeyBytes) -> Result<&PublicKey, Error> {
+        if self.keys.contains_key(bytes) {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+            let public_key = bytes.decompress()?;
+            self.insert_entry(*bytes, CachedPublicKey { public_key, index: None });
+        }
+
+        Ok(&self.keys[bytes].public_key)
+    }
+
+    /// Get the validator index of some compressed bytes, if they were inserted with one.
+    pub fn get_index(&mut self, bytes: &PublicKeyBytes) -> Option<u64> {
+        match self.keys.get(bytes) {
+            Some(cached) => {
+                self.hits += 1;
+                cached.index
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Insert a PublicKey with an optional validator index.
+    ///
+    /// Pre-requsites: The PublicKey must be valid.
+    pub fn insert(&mut self, public_key: PublicKey, index: Option<u64>) {
+        let bytes = PublicKeyBytes::from(&public_key);
+        self.insert_entry(bytes, CachedPublicKey { public_key, index });
+    }
+
+    /// Insert PublicKeys from uncompressed (x, y) bytes where each PublicKey's validator index is
+    /// its position in `keys`.
+    ///
+    /// Uses `PublicKey::from_uncompressed_bytes` which does not validate the keys, MUST only be
+    /// used on verified keys such as those loaded from a local database.
+    pub fn load_uncompressed<'a, I>(&mut self, keys: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        for (index, bytes) in keys.into_iter().enumerate() {
+            let public_key = PublicKey::from_uncompressed_bytes(bytes)?;
+            self.insert(public_key, Some(index as u64));
+        }
+        Ok(())
+    }
+
+    /// Returns true if the compressed bytes are in the cache, does not affect the stats.
+    pub fn contains(&self, bytes: &PublicKeyBytes) -> bool {
+        self.keys.contains_key(bytes)
+    }
+
+    /// The number of PublicKeys in the cache.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if the cache contains no PublicKeys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The maximum number of PublicKeys in the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of lookups which found their PublicKey in the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of lookups which did not find their PublicKey in the cache.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    // Insert or replace an entry, evicting the oldest entries while the cache is full.
+    fn insert_entry(&mut self, bytes: PublicKeyBytes, cached: CachedPublicKey) {
+        if let Some(existing) = self.keys.get_mut(&bytes) {
+            *existing = cached;
+            return;
+        }
+
+        while self.keys.len() >= self.capacity {
+            match self.insertion_order.pop_front() {
+                Some(oldest) => {
+                    self.keys.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.insertion_order.push_back(bytes);
+        self.keys.insert(bytes, cached);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::super::amcl_utils::G1_BYTES;
+    use super::super::keys::Keypair;
+    use super::*;
+
+    fn random_public_keys(n: usize) -> Vec<PublicKey> {
+        (0..n).map(|_| Keypair::random(&mut rand::thread_rng()).pk).collect()
+    }
+
+    #[test]
+    fn test_get_hits_and_misses() {
+        let public_keys = random_public_keys(3);
+        let mut cache = PublicKeyCache::new(10);
+        assert!(cache.is_empty());
+
+        for public_key in public_keys.iter() {
+            let bytes = PublicKeyBytes::from(public_key);
+            assert_eq!(cache.get(&bytes), Ok(public_key));
+            assert_eq!(cache.get(&bytes), Ok(public_key));
+            assert_eq!(cache.get_index(&bytes), None);
+        }
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.hits(), 6);
+        assert_eq!(cache.misses(), 3);
+    }
+
+    #[test]
+    fn test_get_invalid() {
+        let mut cache = PublicKeyCache::new(10);
+
+        // Infinity is not a valid PublicKey
+        let mut infinity = [0u8; 48];
+        infinity[0] = 0xc0;
+        let infinity = PublicKeyBytes::from(infinity);
+        assert_eq!(cache.get(&infinity), Err(Error::InfinityPublicKey));
+        assert!(!cache.contains(&infinity));
+        assert_eq!(cache.misses(), 1);
+
+        // Invalid points are not cached
+        assert!(cache.get(&PublicKeyBytes::from([0xff; 48])).is_err());
+        assert!(cache.is_empty());
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let public_keys = random_public_keys(4);
+        let bytes: Vec<PublicKeyBytes> = public_keys.iter().map(PublicKeyBytes::from).collect();
+        let mut cache = PublicKeyCache::new(3);
+
+        for b in bytes.iter() {
+            cache.get(b).unwrap();
+        }
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.capacity(), 3);
+        assert!(!cache.contains(&bytes[0]));
+        assert!(bytes[1..].iter().all(|b| cache.contains(b)));
+
+        // Re-inserting an existing key does not evict
+        cache.insert(public_keys[1].clone(), Some(1));
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.get_index(&bytes[1]), Some(1));
+
+        // A cache always holds at least one PublicKey
+        let mut cache = PublicKeyCache::new(0);
+        assert_eq!(cache.capacity(), 1);
+        assert_eq!(cache.get(&bytes[0]), Ok(&public_keys[0]));
+        assert_eq!(cache.get(&bytes[1]), Ok(&public_keys[1]));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_load_uncompressed() {
+        let public_keys = random_public_keys(5);
+        let uncompressed: Vec<Vec<u8>> =
+            public_keys.iter().map(|pk| pk.as_uncompressed_bytes().to_vec()).collect();
+
+        let mut cache = PublicKeyCache::new(10);
+        cache.load_uncompressed(uncompressed.iter().map(|bytes| bytes.as_slice())).unwrap();
+        assert_eq!(cache.len(), 5);
+
+        for (index, public_key) in public_keys.iter().enumerate() {
+            let bytes = PublicKeyBytes::from(public_key);
+            assert_eq!(cache.get_index(&bytes), Some(index as u64));
+            assert_eq!(cache.get(&bytes), Ok(public_key));
+        }
+        assert_eq!(cache.hits(), 10);
+        assert_eq!(cache.misses(), 0);
+
+        // Unknown keys miss
+        let other = PublicKeyBytes::from(&random_public_keys(1)[0]);
+        assert_eq!(cache.get_index(&other), None);
+        assert_eq!(cache.misses(), 1);
+
+        // Incorrect lengths error
+        assert_eq!(
+            cache.load_uncompressed(vec![&[0u8; 95][..]]),
+            Err(Error::InvalidLength { expected: G1_BYTES * 2 })
+        );
+    }
+}
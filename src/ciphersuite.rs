@@ -0,0 +1,285 @@
+extern crate amcl;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use super::amcl_utils::{hash_to_curve_g1_with_dst, hash_to_curve_g2_with_dst, GroupG1, GroupG2};
+use super::keys::{MinSigPublicKey, VerifyingKey};
+
+/// A BLS ciphersuite.
+///
+/// Each scheme is defined for both the minimal-pubkey-size variant (signatures in G2 and
+/// PublicKeys in G1) and the minimal-signature-size variant (signatures in G1 and PublicKeys
+/// in G2). Defines how messages are hashed to curve and which checks are required during verification.
+/// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-4.2
+pub trait Ciphersuite {
+    /// Domain separation tag used when hashing messages to G2.
+    const DST: &'static [u8];
+    /// Domain separation tag used when hashing messages to G1 for minimal-signature-size.
+    const MIN_SIG_DST: &'static [u8];
+    /// AggregateVerify requires all messages to be distinct.
+    const DISTINCT_MESSAGES: bool;
+    /// Messages are prepended with the signer's compressed PublicKey.
+    const AUGMENT_MESSAGES: bool;
+}
+
+/// Basic scheme, rogue key attacks are prevented by requiring distinct messages.
+///
+/// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3.1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BasicScheme;
+
+impl Ciphersuite for BasicScheme {
+    const DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+    const MIN_SIG_DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+    const DISTINCT_MESSAGES: bool = true;
+    const AUGMENT_MESSAGES: bool = false;
+}
+
+/// Message Augmentation scheme, rogue key attacks are prevented by signing the PublicKey
+/// along with each message.
+///
+/// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3.2
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MessageAugmentationScheme;
+
+impl Ciphersuite for MessageAugmentationScheme {
+    const DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_";
+    const MIN_SIG_DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_AUG_";
+    const DISTINCT_MESSAGES: bool = false;
+    const AUGMENT_MESSAGES: bool = true;
+}
+
+/// Proof of Possession scheme, rogue key attacks are prevented by verifying a
+/// ProofOfPossession for each PublicKey. This is the scheme used by Ethereum 2.0.
+///
+/// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3.3
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProofOfPossessionScheme;
+
+impl Ciphersuite for ProofOfPossessionScheme {
+    const DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    const MIN_SIG_DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const DISTINCT_MESSAGES: bool = false;
+    const AUGMENT_MESSAGES: bool = false;
+}
+
+// Take a message signed by some PublicKey and convert it to a GroupG2 point
+// using the hashing rules of the Ciphersuite.
+pub(crate) fn hash_message<C: Ciphersuite>(msg: &[u8], pk: &impl VerifyingKey) -> GroupG2 {
+    if C::AUGMENT_MESSAGES {
+        // PK || msg
+        let mut augmented_msg = pk.compressed_bytes().to_vec();
+        augmented_msg.extend_from_slice(msg);
+        hash_to_curve_g2_with_dst(&augmented_msg, C::DST)
+    } else {
+        hash_to_curve_g2_with_dst(msg, C::DST)
+    }
+}
+
+// Take a message signed by some MinSigPublicKey and convert it to a GroupG1 point
+// using the hashing rules of the Ciphersuite.
+pub(crate) fn hash_message_min_sig<C: Ciphersuite>(msg: &[u8], pk: &MinSigPublicKey) -> GroupG1 {
+    if C::AUGMENT_MESSAGES {
+        // PK || msg
+        let mut augmented_msg = pk.as_bytes().to_vec();
+        augmented_msg.extend_from_slice(msg);
+        hash_to_curve_g1_with_dst(&augmented_msg, C::MIN_SIG_DST)
+    } else {
+        hash_to_curve_g1_with_dst(msg, C::MIN_SIG_DST)
+    }
+}
+
+// Returns true if there are no repeated messages.
+pub(crate) fn messages_are_distinct(msgs: &[&[u8]]) -> bool {
+    let mut sorted_msgs: Vec<&[u8]> = msgs.to_vec();
+    sorted_msgs.sort_unstable();
+    sorted_msgs.windows(2).all(|pair| pair[0] != pair[1])
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate hex;
+    extern crate rand;
+
+    use super::super::aggregates::AggregateSignature;
+    use super::super::keys::{Keypair, PublicKey, SecretKey};
+    use super::super::signature::{MinSigSignature, Signature};
+    use super::*;
+
+    // Known Signatures were generated using blst with the SecretKey of the README.
+    const SK: &str = "4efc7a7e20004b59fc1f2a82fe58065a8aca87c2e975b54b60ee4f64ed3b8c6f";
+
+    fn check_known_signatures<C: Ciphersuite>(vectors: &[(&str, &str)]) {
+        let sk = SecretKey::from_bytes(&hex::decode(SK).unwrap()).unwrap();
+        let pk = PublicKey::from_secret_key(&sk);
+
+        for (msg, expected) in vectors {
+            let signature = Signature::new_with_ciphersuite::<C>(msg.as_bytes(), &sk);
+            assert_eq!(signature.as_bytes().to_vec(), hex::decode(expected).unwrap());
+            assert!(signature.verify_with_ciphersuite::<C>(msg.as_bytes(), &pk));
+        }
+    }
+
+    fn check_known_min_sig_signatures<C: Ciphersuite>(vectors: &[(&str, &str)]) {
+        let sk = SecretKey::from_bytes(&hex::decode(SK).unwrap()).unwrap();
+        let pk = MinSigPublicKey::from_secret_key(&sk);
+
+        for (msg, expected) in vectors {
+            let signature = MinSigSignature::new_with_ciphersuite::<C>(msg.as_bytes(), &sk);
+            assert_eq!(signature.as_bytes().to_vec(), hex::decode(expected).unwrap());
+            assert!(signature.verify_with_ciphersuite::<C>(msg.as_bytes(), &pk));
+        }
+    }
+
+    #[test]
+    fn test_basic_known_signatures() {
+        check_known_signatures::<BasicScheme>(&[
+            ("", "ace67ae5860f856c4a8a933f20460ddecc61699ad9e58ca1c5976117b17252d472b55dc649d8f46a60ab554637abdfc01484769f87f89ff4215f0a887909363417a5ac67d918c2bc62ee00a5526b027befddaabedf3f77249bbfb9be27c7a729"),
+            ("cats", "b0069d7c5f305071692fb9b1fe9459da7bfae85a1462f4601a2734125ec36d54ba120488ee37f18d7149d8941155caf100a1584cd02eeaf7ad0b9d3803d4c30148812a6fa1072d2759150c566f675788970bfe272fca214a7c83a45a8531314e"),
+            ("abc", "a5ac072397f3dba7ed58708a66fe90eb778f4920050a767c65bde9a8fcb63ddf0b3d9dbf789f1aa93c6271985f6ac4e4005f50d667555cd3b868ec5cd8ad30a1bad817f7d697302c18fb70dd0684c842c9ce859f5ca8ff74220669af2ae15bfd"),
+        ]);
+    }
+
+    #[test]
+    fn test_message_augmentation_known_signatures() {
+        check_known_signatures::<MessageAugmentationScheme>(&[
+            ("", "b88bbabfddf76c2194694a234d60e72a7a7df89f9281453a1d8327ad4cdbecccc4ac8dcdcdbe0c4a7e1cf85bfadfe70e19937e2f83c6dd6845aae31dd95bb4644497a2b0ce39b4b41b73cf3539b1f4f13932f0cdefdd83f2722156de3145e39b"),
+            ("cats", "80f570933f25977ef9bd6eeaae5ba02d686544e4520949fdaee8cb267395cfa891c605816518e8ab3a98bd62d2a6057b150db0853a5ec70b75239c28a5b66bb9063f70b0743a412769e2281e838414c75ddf2801fb30cc319a19b5c26332aaaf"),
+            ("abc", "967ff9f5991fb940cdc8604f1b130c85ac6cf6ba4ba13362ab23ba41883450b04b437babf43c825e01f5e79084c230450fe4ce90363c343c7f71ac03b427236dcefa5ca0d15be43f9cb8ebf24f30fd61d3949ea73745bc39c8de6b33f70f377e"),
+        ]);
+    }
+
+    #[test]
+    fn test_proof_of_possession_known_signatures() {
+        check_known_signatures::<ProofOfPossessionScheme>(&[
+            ("", "907015bdd8b4571bbbb66f136cfa221da8e2a515170e463fd81cb06964c9625b85f25161ab782d27fcb48b036ecb6e720efa5bdd59c79a1c7b5da0d73d6cdb98a49bdd4623b56ccb9678fe2452d528e6ea6a4d7f9f935f9c591e5c423a0cbd47"),
+            ("cats", "add2bac2b18eacbb8e80dab65a17c54f4af098ebf136a2e55a8ed9ff4a6a2a1e012e2a6da0cf6c09c7bc97076c6affae173f90ccfb783ec078939b07b59e234a2f025b606c4cdf0432b0e62718cd11a356684e88c89ba9a83e87cb15afa12fa0"),
+            ("abc", "88e3587115881ce5c0799a55861bfca63fbd0662f47b0f0e6c647e9168df0779659769580f73c7da7d105ced7d97a551018267c832de2b74028f15538e68ca1d3b04d8436bf7ae304b1f38e336cd4c2ff3c03162c47fa4c6dd60d4db895e7f24"),
+        ]);
+
+        // Signature::new() defaults to the Proof of Possession scheme
+        let sk = SecretKey::from_bytes(&hex::decode(SK).unwrap()).unwrap();
+        let signature = Signature::new(b"cats", &sk);
+        assert_eq!(
+            signature,
+            Signature::new_with_ciphersuite::<ProofOfPossessionScheme>(b"cats", &sk)
+        );
+    }
+
+    #[test]
+    fn test_min_sig_known_signatures() {
+        check_known_min_sig_signatures::<BasicScheme>(&[
+            ("", "a7c0b46d7da06bd6c51dab3834c9ea43004cedf41742a433fef01d729d77001f82bc7998b27aa3eff4cf6955a597f391"),
+            ("cats", "b8aa7e5816066c6895d0bea5cfd4d6cef4bb3e8c22af65faf48599e32278070dc87ac4dff7dd54535c284d8a8f11ae08"),
+            ("abc", "b6d8e2d407ad1a3248ed3615808873221c27a40be2facfc2b1e9c50d4662631bf0378c850ec384a12e8ff41ec7a2daef"),
+        ]);
+        check_known_min_sig_signatures::<MessageAugmentationScheme>(&[
+            ("", "88f0ef68135a69fad8601cb7dc542289de44c421a1d497de0f2b431c313e54358c687a2b6b8c260bb1796aad561222d4"),
+            ("cats", "957ddfb328c413c7a55236cdcaa8b805ec0894a3b551f72914969d794ff476d40e70a4f4e1b458d0b6ba37d79c399cf5"),
+            ("abc", "95b974a1fc5acda5ec18a5b77938cae52c3f7ce8ed140805dbab07f04b54e6149a99a38fbd1a4cad3affc0ee8c23dd95"),
+        ]);
+        check_known_min_sig_signatures::<ProofOfPossessionScheme>(&[
+            ("", "834e54af536ec3b39800a34849ed123146c69c634f412b0c1528034600a87fb281ff5338713d8cff285ffe5d58b59dd3"),
+            ("cats", "b4caed01316c4ee026147c1ac7c8ac0007fdb2763f63d59ca6806f3046eb1aea3ea2ac3379e2bc293ef59d7d681976ad"),
+            ("abc", "8cb1005385bac5ed26d916747c17ec8ceab645905ee21759cd2d4795c0576cac8ac55f10c9dcd37046e3330b02506365"),
+        ]);
+    }
+
+    #[test]
+    fn test_ciphersuites_are_domain_separated() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = b"cats";
+
+        let basic = Signature::new_with_ciphersuite::<BasicScheme>(msg, &keypair.sk);
+        let aug = Signature::new_with_ciphersuite::<MessageAugmentationScheme>(msg, &keypair.sk);
+        let pop = Signature::new_with_ciphersuite::<ProofOfPossessionScheme>(msg, &keypair.sk);
+
+        assert!(!basic.verify_with_ciphersuite::<MessageAugmentationScheme>(msg, &keypair.pk));
+        assert!(!basic.verify_with_ciphersuite::<ProofOfPossessionScheme>(msg, &keypair.pk));
+        assert!(!aug.verify_with_ciphersuite::<BasicScheme>(msg, &keypair.pk));
+        assert!(!aug.verify_with_ciphersuite::<ProofOfPossessionScheme>(msg, &keypair.pk));
+        assert!(!pop.verify_with_ciphersuite::<BasicScheme>(msg, &keypair.pk));
+        assert!(!pop.verify_with_ciphersuite::<MessageAugmentationScheme>(msg, &keypair.pk));
+    }
+
+    // Sign msgs[i] with keypairs[i] under the Ciphersuite and aggregate the Signatures.
+    fn aggregate_signatures<C: Ciphersuite>(
+        msgs: &[&[u8]],
+        keypairs: &[Keypair],
+    ) -> AggregateSignature {
+        let mut aggregate_signature = AggregateSignature::new();
+        for (msg, keypair) in msgs.iter().zip(keypairs) {
+            aggregate_signature.add(&Signature::new_with_ciphersuite::<C>(msg, &keypair.sk));
+        }
+        aggregate_signature
+    }
+
+    #[test]
+    fn test_aggregate_verify_ciphersuites() {
+        let keypairs: Vec<Keypair> =
+            (0..4).map(|_| Keypair::random(&mut rand::thread_rng())).collect();
+        let public_keys: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let msgs: Vec<&[u8]> = vec![b"zero", b"one", b"two", b"three"];
+
+        let basic = aggregate_signatures::<BasicScheme>(&msgs, &keypairs);
+        assert!(basic.aggregate_verify_with_ciphersuite::<BasicScheme>(&msgs, &public_keys));
+
+        let aug = aggregate_signatures::<MessageAugmentationScheme>(&msgs, &keypairs);
+        assert!(
+            aug.aggregate_verify_with_ciphersuite::<MessageAugmentationScheme>(&msgs, &public_keys)
+        );
+        assert!(!aug.aggregate_verify_with_ciphersuite::<BasicScheme>(&msgs, &public_keys));
+
+        let pop = aggregate_signatures::<ProofOfPossessionScheme>(&msgs, &keypairs);
+        assert!(
+            pop.aggregate_verify_with_ciphersuite::<ProofOfPossessionScheme>(&msgs, &public_keys)
+        );
+        assert!(pop.aggregate_verify(&msgs, &public_keys));
+    }
+
+    #[test]
+    fn test_aggregate_verify_repeated_messages() {
+        let keypairs: Vec<Keypair> =
+            (0..3).map(|_| Keypair::random(&mut rand::thread_rng())).collect();
+        let public_keys: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        let msgs: Vec<&[u8]> = vec![b"cats", b"dogs", b"cats"];
+
+        // Basic scheme must reject repeated messages
+        let basic = aggregate_signatures::<BasicScheme>(&msgs, &keypairs);
+        assert!(!basic.aggregate_verify_with_ciphersuite::<BasicScheme>(&msgs, &public_keys));
+
+        // Message Augmentation scheme allows repeated messages
+        let aug = aggregate_signatures::<MessageAugmentationScheme>(&msgs, &keypairs);
+        assert!(
+            aug.aggregate_verify_with_ciphersuite::<MessageAugmentationScheme>(&msgs, &public_keys)
+        );
+    }
+
+    #[test]
+    fn test_messages_are_distinct() {
+        assert!(messages_are_distinct(&[]));
+        assert!(messages_are_distinct(&[b"a"]));
+        assert!(messages_are_distinct(&[b"a", b"b", b"ab"]));
+        assert!(!messages_are_distinct(&[b"a", b"b", b"a"]));
+        assert!(!messages_are_distinct(&[b"", b""]));
+    }
+
+    #[test]
+    fn test_readme() {
+        // This is an exact replica of the README.md at the top level.
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let message = "cats".as_bytes();
+
+        // Sign and verify a message using the Message Augmentation scheme.
+        let signature =
+            Signature::new_with_ciphersuite::<MessageAugmentationScheme>(message, &keypair.sk);
+        assert!(
+            signature.verify_with_ciphersuite::<MessageAugmentationScheme>(message, &keypair.pk)
+        );
+
+        // The signature is not valid under any other scheme.
+        assert!(!signature.verify_with_ciphersuite::<BasicScheme>(message, &keypair.pk));
+        assert!(!signature.verify(message, &keypair.pk));
+    }
+}
@@ -0,0 +1,228 @@
+extern crate amcl;
+
+use super::amcl_utils::{G1_BYTES, G2_BYTES};
+use super::error::Error;
+use super::keys::PublicKey;
+use super::signature::Signature;
+
+/// A compressed PublicKey which is only decompressed and validated when required.
+///
+/// Cheap to store, compare, hash and forward.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct PublicKeyBytes([u8; G1_BYTES]);
+
+impl PublicKeyBytes {
+    /// Instantiate PublicKeyBytes from compressed bytes, only the length is checked.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != G1_BYTES {
+            return Err(Error::InvalidLength { expected: G1_BYTES });
+        }
+        let mut public_key_bytes = [0u8; G1_BYTES];
+        public_key_bytes.copy_from_slice(bytes);
+        Ok(PublicKeyBytes(public_key_bytes))
+    }
+
+    /// Export the compressed bytes.
+    pub fn as_bytes(&self) -> [u8; G1_BYTES] {
+        self.0
+    }
+
+    /// Decompress and validate the PublicKey.
+    pub fn decompress(&self) -> Result<PublicKey, Error> {
+        PublicKey::from_bytes(&self.0)
+    }
+}
+
+impl From<[u8; G1_BYTES]> for PublicKeyBytes {
+    fn from(bytes: [u8; G1_BYTES]) -> Self {
+        PublicKeyBytes(bytes)
+    }
+}
+
+impl<'a> From<&'a PublicKey> for PublicKeyBytes {
+    fn from(public_key: &'a PublicKey) -> Self {
+        PublicKeyBytes(public_key.as_bytes())
+    }
+}
+
+impl AsRef<[u8]> for PublicKeyBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A compressed Signature which is only decompressed when required.
+///
+/// Cheap to store, compare, hash and forward.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct SignatureBytes([u8; G2_BYTES]);
+
+impl SignatureBytes {
+    /// Instantiate SignatureBytes from compressed bytes, only the length is checked.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != G2_BYTES {
+            return Err(Error::InvalidLength { expected: G2_BYTES });
+        }
+        let mut signature_bytes = [0u8; G2_BYTES];
+        signature_bytes.copy_from_slice(bytes);
+        Ok(SignatureBytes(signature_bytes))
+    }
+
+    /// Export the compressed bytes.
+    pub fn as_bytes(&self) -> [u8; G2_BYTES] {
+        self.0
+    }
+
+    /// Decompress the Signature.
+    ///
+    /// As with `Signature::from_bytes` the subgroup is checked during verification.
+    pub fn decompress(&self) -> Result<Signature, Error> {
+        Signature::from_bytes(&self.0)
+    }
+}
+
+impl From<[u8; G2_BYTES]> for SignatureBytes {
+    fn from(bytes: [u8; G2_BYTES]) -> Self {
+        SignatureBytes(bytes)
+    }
+}
+
+impl<'a> From<&'a Signature> for SignatureBytes {
+    fn from(signature: &'a Signature) -> Self {
+        SignatureBytes(signature.as_bytes())
+    }
+}
+
+impl AsRef<[u8]> for SignatureBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::super::aggregates::{AggregatePublicKey, AggregateSignature};
+    use super::super::keys::Keypair;
+    use super::*;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn test_public_key_bytes_round_trip() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let public_key_bytes = PublicKeyBytes::from(&keypair.pk);
+
+        assert_eq!(public_key_bytes.as_bytes(), keypair.pk.as_bytes());
+        assert_eq!(public_key_bytes.as_ref(), &keypair.pk.as_bytes()[..]);
+        assert_eq!(PublicKeyBytes::from_bytes(public_key_bytes.as_ref()), Ok(public_key_bytes));
+        assert_eq!(PublicKeyBytes::from(keypair.pk.as_bytes()), public_key_bytes);
+        assert_eq!(public_key_bytes.decompress(), Ok(keypair.pk));
+    }
+
+    #[test]
+    fn test_signature_bytes_round_trip() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", &keypair.sk);
+        let signature_bytes = SignatureBytes::from(&signature);
+
+        assert_eq!(signature_bytes.as_bytes().to_vec(), signature.as_bytes().to_vec());
+        assert_eq!(SignatureBytes::from_bytes(signature_bytes.as_ref()), Ok(signature_bytes));
+        assert_eq!(SignatureBytes::from(signature.as_bytes()), signature_bytes);
+        assert_eq!(signature_bytes.decompress(), Ok(signature));
+    }
+
+    #[test]
+    fn test_invalid_bytes_are_lazy() {
+        // Lengths are checked eagerly
+        assert_eq!(
+            PublicKeyBytes::from_bytes(&[0; G1_BYTES - 1]),
+            Err(Error::InvalidLength { expected: G1_BYTES })
+        );
+        assert_eq!(
+            SignatureBytes::from_bytes(&[0; G1_BYTES]),
+            Err(Error::InvalidLength { expected: G2_BYTES })
+        );
+
+        // Points are only checked when decompressed
+        let public_key_bytes = PublicKeyBytes::from_bytes(&[0xff; G1_BYTES]).unwrap();
+        assert!(public_key_bytes.decompress().is_err());
+        let signature_bytes = SignatureBytes::from_bytes(&[0xff; G2_BYTES]).unwrap();
+        assert!(signature_bytes.decompress().is_err());
+
+        // Infinity is a valid Signature but not a valid PublicKey
+        let mut infinity = [0u8; G2_BYTES];
+        infinity[0] = 0xc0;
+        assert!(SignatureBytes::from(infinity).decompress().is_ok());
+        let mut infinity = [0u8; G1_BYTES];
+        infinity[0] = 0xc0;
+        assert_eq!(PublicKeyBytes::from(infinity).decompress(), Err(Error::InfinityPublicKey));
+    }
+
+    #[test]
+    fn test_bytes_hash_and_ord() {
+        let keypairs: Vec<Keypair> =
+            (0..5).map(|_| Keypair::random(&mut rand::thread_rng())).collect();
+        let mut public_key_bytes: Vec<PublicKeyBytes> =
+            keypairs.iter().map(|kp| PublicKeyBytes::from(&kp.pk)).collect();
+        public_key_bytes.push(public_key_bytes[0]);
+
+        let hash_set: HashSet<PublicKeyBytes> = public_key_bytes.iter().cloned().collect();
+        let btree_set: BTreeSet<PublicKeyBytes> = public_key_bytes.iter().cloned().collect();
+        assert_eq!(hash_set.len(), 5);
+        assert_eq!(btree_set.len(), 5);
+
+        // Ordered as bytes
+        public_key_bytes.sort();
+        assert!(public_key_bytes.windows(2).all(|pair| pair[0].as_bytes() <= pair[1].as_bytes()));
+
+        let signature_bytes: HashSet<SignatureBytes> = keypairs
+            .iter()
+            .map(|kp| SignatureBytes::from(&Signature::new(b"cats", &kp.sk)))
+            .collect();
+        assert_eq!(signature_bytes.len(), 5);
+    }
+
+    #[test]
+    fn test_aggregate_bytes() {
+        let keypairs: Vec<Keypair> =
+            (0..5).map(|_| Keypair::random(&mut rand::thread_rng())).collect();
+        let msg = b"cats";
+        let signatures: Vec<Signature> =
+            keypairs.iter().map(|kp| Signature::new(msg, &kp.sk)).collect();
+
+        let public_key_bytes: Vec<PublicKeyBytes> =
+            keypairs.iter().map(|kp| PublicKeyBytes::from(&kp.pk)).collect();
+        let signature_bytes: Vec<SignatureBytes> =
+            signatures.iter().map(SignatureBytes::from).collect();
+
+        let aggregate_public_key =
+            AggregatePublicKey::aggregate_bytes(&public_key_bytes.iter().collect::<Vec<_>>())
+                .unwrap();
+        let aggregate_signature =
+            AggregateSignature::aggregate_bytes(&signature_bytes.iter().collect::<Vec<_>>())
+                .unwrap();
+
+        let public_keys: Vec<&PublicKey> = keypairs.iter().map(|kp| &kp.pk).collect();
+        assert_eq!(aggregate_public_key, AggregatePublicKey::aggregate(&public_keys).unwrap());
+        assert_eq!(
+            aggregate_signature,
+            AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>())
+        );
+        assert!(
+            aggregate_signature.fast_aggregate_verify_pre_aggregated(msg, &aggregate_public_key)
+        );
+
+        // Invalid and empty inputs
+        let invalid_public_key = PublicKeyBytes::from([0xff; G1_BYTES]);
+        assert!(AggregatePublicKey::aggregate_bytes(&[&public_key_bytes[0], &invalid_public_key])
+            .is_err());
+        assert_eq!(AggregatePublicKey::aggregate_bytes(&[]), Err(Error::EmptyAggregate));
+        let invalid_signature = SignatureBytes::from([0xff; G2_BYTES]);
+        assert!(AggregateSignature::aggregate_bytes(&[&signature_bytes[0], &invalid_signature])
+            .is_err());
+        assert_eq!(AggregateSignature::aggregate_bytes(&[]), Ok(AggregateSignature::new()));
+    }
+}
@@ -0,0 +1,390 @@
+extern crate amcl;
+extern crate zeroize;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::{fmt, str::FromStr};
+#[cfg(feature = "std")]
+use std::{fmt, str::FromStr};
+
+use self::zeroize::Zeroize;
+use super::amcl_utils::SECRET_KEY_BYTES;
+use super::error::Error;
+use super::keys::SecretKey;
+
+use amcl::hash256::HASH256;
+
+// Key Derivation Constants
+/// Number of 32 byte chunks in a Lamport SecretKey.
+pub const LAMPORT_CHUNKS: usize = 255;
+/// Length in bytes of each Lamport SecretKey chunk.
+pub const LAMPORT_CHUNK_BYTES: usize = 32;
+/// Minimum length in bytes of a seed for `derive_master_sk`.
+pub const MIN_SEED_BYTES: usize = 32;
+
+// Derivation Path Constants
+/// EIP-2334 purpose, the BLS12-381 curve.
+pub const PURPOSE: u32 = 12381;
+/// EIP-2334 coin type of Ethereum.
+pub const COIN_TYPE: u32 = 3600;
+
+/// Errors when parsing a DerivationPath.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DerivationPathError {
+    /// The path does not begin with the master node `m`.
+    InvalidMaster,
+    /// A path node is not a decimal u32.
+    InvalidIndex,
+}
+
+impl fmt::Display for DerivationPathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DerivationPathError::InvalidMaster => write!(f, "path must begin with m"),
+            DerivationPathError::InvalidIndex => write!(f, "path index is not a decimal u32"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DerivationPathError {}
+
+/// An EIP-2334 path of child indices from the master SecretKey, e.g. `m/12381/3600/0/0/0`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DerivationPath {
+    indices: Vec<u32>,
+}
+
+impl DerivationPath {
+    /// Instantiate a DerivationPath from the child indices below the master node.
+    pub fn new(indices: &[u32]) -> Self {
+        Self { indices: indices.to_vec() }
+    }
+
+    /// The path `m/12381/3600/i/0` of the withdrawal key of validator `i`.
+    ///
+    /// https://eips.ethereum.org/EIPS/eip-2334#validator-keys
+    pub fn withdrawal(validator_index: u32) -> Self {
+        Self::new(&[PURPOSE, COIN_TYPE, validator_index, 0])
+    }
+
+    /// The path `m/12381/3600/i/0/0` of the signing key of validator `i`.
+    ///
+    /// https://eips.ethereum.org/EIPS/eip-2334#validator-keys
+    pub fn signing(validator_index: u32) -> Self {
+        Self::new(&[PURPOSE, COIN_TYPE, validator_index, 0, 0])
+    }
+
+    /// The child indices below the master node.
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = DerivationPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut nodes = path.split('/').map(str::trim);
+
+        if nodes.next() != Some("m") {
+            return Err(DerivationPathError::InvalidMaster);
+        }
+
+        let indices = nodes
+            .map(|node| {
+                // Reject signs and empty nodes which `u32::from_str` would otherwise accept
+                if node.is_empty() || !node.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(DerivationPathError::InvalidIndex);
+                }
+                node.parse::<u32>().map_err(|_| DerivationPathError::InvalidIndex)
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        Ok(Self { indices })
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.indices {
+            write!(f, "/{}", index)?;
+        }
+        Ok(())
+    }
+}
+
+/// Derive Master SecretKey
+///
+/// Derive the root SecretKey of an EIP-2333 key tree from a seed of at least 32 bytes.
+/// https://eips.ethereum.org/EIPS/eip-2333#derive_master_sk
+pub fn derive_master_sk(seed: &[u8]) -> Result<SecretKey, Error> {
+    if seed.len() < MIN_SEED_BYTES {
+        return Err(Error::TooShort { min: MIN_SEED_BYTES });
+    }
+    hkdf_mod_r(seed)
+}
+
+/// Derive Child SecretKey
+///
+/// Derive the child SecretKey at `index` of a parent SecretKey.
+/// https://eips.ethereum.org/EIPS/eip-2333#derive_child_sk
+pub fn derive_child_sk(parent_sk: &SecretKey, index: u32) -> SecretKey {
+    let compressed_lamport_pk = parent_sk_to_lamport_pk(parent_sk, index);
+    hkdf_mod_r(&compressed_lamport_pk).unwrap() // will only error if ikm < 32 bytes
+}
+
+/// Derive Path
+///
+/// Derive the SecretKey at some DerivationPath from a seed of at least 32 bytes.
+/// https://eips.ethereum.org/EIPS/eip-2334
+pub fn derive_path(seed: &[u8], path: &DerivationPath) -> Result<SecretKey, Error> {
+    let mut sk = derive_master_sk(seed)?;
+    for index in path.indices() {
+        sk = derive_child_sk(&sk, *index);
+    }
+    Ok(sk)
+}
+
+// HKDF_mod_r is the KeyGen of the BLS signature standard with an empty key_info.
+fn hkdf_mod_r(ikm: &[u8]) -> Result<SecretKey, Error> {
+    SecretKey::key_generate(ikm, &[])
+}
+
+// Compress the Lamport PublicKey of a parent SecretKey to 32 bytes.
+// https://eips.ethereum.org/EIPS/eip-2333#parent_sk_to_lamport_pk
+fn parent_sk_to_lamport_pk(parent_sk: &SecretKey, index: u32) -> [u8; 32] {
+    let salt = index.to_be_bytes();
+    let mut ikm = parent_sk.as_bytes();
+    let mut not_ikm = [0u8; SECRET_KEY_BYTES];
+    for (not_byte, byte) in not_ikm.iter_mut().zip(ikm.iter()) {
+        *not_byte = !byte;
+    }
+
+    let mut lamport_0 = ikm_to_lamport_sk(&ikm, &salt);
+    let mut lamport_1 = ikm_to_lamport_sk(&not_ikm, &salt);
+    ikm.zeroize();
+    not_ikm.zeroize();
+
+    // lamport_PK = H(lamport_0[0]) || ... || H(lamport_1[254])
+    let mut lamport_pk = Vec::<u8>::with_capacity(2 * LAMPORT_CHUNKS * LAMPORT_CHUNK_BYTES);
+    for chunk in lamport_0.chunks(LAMPORT_CHUNK_BYTES).chain(lamport_1.chunks(LAMPORT_CHUNK_BYTES))
+    {
+        lamport_pk.extend_from_slice(&sha256(chunk));
+    }
+    lamport_0.zeroize();
+    lamport_1.zeroize();
+
+    sha256(&lamport_pk)
+}
+
+// Expand some IKM into a Lamport SecretKey of 255 32 byte chunks.
+// https://eips.ethereum.org/EIPS/eip-2333#ikm_to_lamport_sk
+fn ikm_to_lamport_sk(ikm: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut prk = HASH256::hkdf_extract(salt, ikm);
+    let okm = hkdf_expand(&prk, &[], LAMPORT_CHUNKS * LAMPORT_CHUNK_BYTES);
+    prk.zeroize();
+    okm
+}
+
+// HKDF-Expand for outputs longer than the 255 bytes supported by `HASH256::hkdf_extend`.
+// https://tools.ietf.org/html/rfc5869#section-2.3
+fn hkdf_expand(prk: &[u8], info: &[u8], l: usize) -> Vec<u8> {
+    // N = ceil(L / HashLen), at most 255
+    let n = l.div_ceil(32);
+    debug_assert!(n <= 255);
+
+    let mut okm = Vec::<u8>::with_capacity(n * 32);
+    let mut t = Vec::<u8>::new();
+
+    // T(i) = HMAC-Hash(PRK, T(i - 1) || info || i)
+    for i in 1..=n {
+        t.extend_from_slice(info);
+        t.push(i as u8);
+        let block = HASH256::hmac(prk, &t);
+        okm.extend_from_slice(&block);
+
+        t.zeroize();
+        t = block.to_vec();
+    }
+    t.zeroize();
+
+    okm.truncate(l);
+    okm
+}
+
+fn sha256(input: &[u8]) -> [u8; 32] {
+    let mut hash256 = HASH256::new();
+    hash256.process_array(input);
+    hash256.hash()
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate hex;
+
+    use super::*;
+
+    // Test vectors from https://eips.ethereum.org/EIPS/eip-2333#test-cases
+    // SecretKeys are the EIP decimal values encoded as 32 byte big endian hex.
+    struct TestVector {
+        seed: &'static str,
+        master_sk: &'static str,
+        child_index: u32,
+        child_sk: &'static str,
+    }
+
+    const TEST_VECTORS: &[TestVector] = &[
+        TestVector {
+            seed: "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            master_sk: "0d7359d57963ab8fbbde1852dcf553fedbc31f464d80ee7d40ae683122b45070",
+            child_index: 0,
+            child_sk: "2d18bd6c14e6d15bf8b5085c9b74f3daae3b03cc2014770a599d8c1539e50f8e",
+        },
+        TestVector {
+            seed: "3141592653589793238462643383279502884197169399375105820974944592",
+            master_sk: "41c9e07822b092a93fd6797396338c3ada4170cc81829fdfce6b5d34bd5e7ec7",
+            child_index: 3141592653,
+            child_sk: "384843fad5f3d777ea39de3e47a8f999ae91f89e42bffa993d91d9782d152a0f",
+        },
+        TestVector {
+            seed: "0099FF991111002299DD7744EE3355BBDD8844115566CC55663355668888CC00",
+            master_sk: "3cfa341ab3910a7d00d933d8f7c4fe87c91798a0397421d6b19fd5b815132e80",
+            child_index: 4294967295,
+            child_sk: "40e86285582f35b28821340f6a53b448588efa575bc4d88c32ef8567b8d9479b",
+        },
+        TestVector {
+            seed: "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            master_sk: "2a0e28ffa5fbbe2f8e7aad4ed94f745d6bf755c51182e119bb1694fe61d3afca",
+            child_index: 42,
+            child_sk: "455c0dc9fccb3395825d92a60d2672d69416be1c2578a87a7a3d3ced11ebb88d",
+        },
+    ];
+
+    #[test]
+    fn test_eip2333_vectors() {
+        for vector in TEST_VECTORS {
+            let seed = hex::decode(vector.seed).unwrap();
+
+            let master_sk = derive_master_sk(&seed).unwrap();
+            assert_eq!(master_sk.as_bytes().to_vec(), hex::decode(vector.master_sk).unwrap());
+
+            let child_sk = derive_child_sk(&master_sk, vector.child_index);
+            assert_eq!(child_sk.as_bytes().to_vec(), hex::decode(vector.child_sk).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_derive_master_sk_short_seed() {
+        assert_eq!(derive_master_sk(&[1u8; 31]), Err(Error::TooShort { min: MIN_SEED_BYTES }));
+        assert!(derive_master_sk(&[1u8; 32]).is_ok());
+    }
+
+    #[test]
+    fn test_hkdf_expand() {
+        // RFC 5869 Test Case 1
+        let prk = hex::decode("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+            .unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let okm = hkdf_expand(&prk, &info, 42);
+        assert_eq!(
+            okm,
+            hex::decode("3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865").unwrap()
+        );
+
+        // Matches HKDF-Expand of amcl for shorter outputs
+        assert_eq!(hkdf_expand(&prk, &info, 255), HASH256::hkdf_extend(&prk, &info, 255));
+
+        // Lamport SecretKey length
+        assert_eq!(hkdf_expand(&prk, &[], 8160).len(), 8160);
+    }
+
+    #[test]
+    fn test_derive_path() {
+        let vector = &TEST_VECTORS[0];
+        let seed = hex::decode(vector.seed).unwrap();
+
+        let path = DerivationPath::new(&[vector.child_index]);
+        let child_sk = derive_path(&seed, &path).unwrap();
+        assert_eq!(child_sk.as_bytes().to_vec(), hex::decode(vector.child_sk).unwrap());
+
+        // The master node is the master SecretKey
+        let master_sk = derive_path(&seed, &"m".parse().unwrap()).unwrap();
+        assert_eq!(master_sk.as_bytes().to_vec(), hex::decode(vector.master_sk).unwrap());
+
+        // Walks each index of the path
+        let path: DerivationPath = "m/12381/3600/0/0".parse().unwrap();
+        let mut sk = derive_master_sk(&seed).unwrap();
+        for index in &[12381, 3600, 0, 0] {
+            sk = derive_child_sk(&sk, *index);
+        }
+        assert_eq!(derive_path(&seed, &path).unwrap(), sk);
+
+        assert_eq!(derive_path(&[1u8; 31], &path), Err(Error::TooShort { min: MIN_SEED_BYTES }));
+    }
+
+    #[test]
+    fn test_derivation_path_from_str() {
+        let path: DerivationPath = "m/12381/3600/0/0/0".parse().unwrap();
+        assert_eq!(path.indices(), &[12381, 3600, 0, 0, 0]);
+        assert_eq!(path, DerivationPath::signing(0));
+
+        // EIP-2334 uses spaces around the separators
+        let path: DerivationPath = "m / 12381 / 3600 / 42 / 0".parse().unwrap();
+        assert_eq!(path, DerivationPath::withdrawal(42));
+
+        let path: DerivationPath = "m/4294967295".parse().unwrap();
+        assert_eq!(path.indices(), &[u32::MAX]);
+
+        let path: DerivationPath = "m".parse().unwrap();
+        assert!(path.indices().is_empty());
+    }
+
+    #[test]
+    fn test_derivation_path_from_str_invalid() {
+        let invalid_master = vec!["", "/0", "M/0", "n/12381", "12381/3600", " /0"];
+        for path in invalid_master {
+            assert_eq!(path.parse::<DerivationPath>(), Err(DerivationPathError::InvalidMaster));
+        }
+
+        let invalid_index =
+            vec!["m/", "m//0", "m/0/", "m/-1", "m/+1", "m/a", "m/0x10", "m/4294967296", "m/1.5"];
+        for path in invalid_index {
+            assert_eq!(path.parse::<DerivationPath>(), Err(DerivationPathError::InvalidIndex));
+        }
+
+        assert_eq!(DerivationPathError::InvalidMaster.to_string(), "path must begin with m");
+    }
+
+    #[test]
+    fn test_derivation_path_display() {
+        assert_eq!(DerivationPath::signing(7).to_string(), "m/12381/3600/7/0/0");
+        assert_eq!(DerivationPath::withdrawal(7).to_string(), "m/12381/3600/7/0");
+        assert_eq!(DerivationPath::new(&[]).to_string(), "m");
+
+        // Round trip
+        let path = "m/12381/3600/4294967295/0/0";
+        assert_eq!(path.parse::<DerivationPath>().unwrap().to_string(), path);
+    }
+
+    #[test]
+    fn test_readme() {
+        // This is an exact replica of the README.md at the top level.
+        let seed = [7u8; 32];
+
+        // Derive the master SecretKey then the child SecretKey at index 0.
+        let master_sk = derive_master_sk(&seed).unwrap();
+        let child_sk = derive_child_sk(&master_sk, 0);
+
+        // Derivation is deterministic.
+        assert_eq!(child_sk, derive_child_sk(&derive_master_sk(&seed).unwrap(), 0));
+
+        // Derive the signing key of validator 0 using an EIP-2334 path.
+        let path: DerivationPath = "m/12381/3600/0/0/0".parse().unwrap();
+        assert_eq!(path, DerivationPath::signing(0));
+        let signing_sk = SecretKey::derive_path(&seed, &path).unwrap();
+        assert_ne!(signing_sk, child_sk);
+    }
+}
@@ -0,0 +1,1376 @@
+extern crate amcl;
+extern crate rand;
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "std")]
+use std::fmt;
+
+use super::amcl_utils::{
+    g1mul, hash_to_curve_g1_with_dst, Big, GroupG1, CURVE_ORDER, G1_BYTES, SECRET_KEY_BYTES,
+};
+use super::error::Error;
+use super::keys::{PublicKey, SecretKey};
+use super::threshold::{public_key_share, verify_share, Polynomial};
+
+use rand::Rng;
+
+// Message Encoding Constants
+const PEDERSEN_COMMITMENTS_TAG: u8 = 0;
+const SHARE_TAG: u8 = 1;
+const COMPLAINT_TAG: u8 = 2;
+const JUSTIFICATION_TAG: u8 = 3;
+const FELDMAN_COMMITMENTS_TAG: u8 = 4;
+const COMMITMENT_COMPLAINT_TAG: u8 = 5;
+const RECONSTRUCTION_TAG: u8 = 6;
+
+// The second Pedersen generator H is hashed to curve so that its discrete logarithm with respect
+// to the G1 generator is unknown.
+const PEDERSEN_GENERATOR_MESSAGE: &[u8] = b"milagro_bls DKG";
+const PEDERSEN_GENERATOR_DST: &[u8] = b"BLS_DKG_PEDERSEN_BLS12381G1_XMD:SHA-256_SSWU_RO_";
+
+/// Errors during distributed key generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DkgError {
+    /// The threshold MUST be at least 1 and at most the number of parties.
+    InvalidThreshold,
+    /// Party indices MUST be in the range [1, n].
+    InvalidIndex(u32),
+    /// The message is not expected in the current round.
+    UnexpectedMessage,
+    /// A private message was delivered to a party it is not addressed to.
+    NotRecipient,
+    /// The message could not be decoded.
+    InvalidMessage,
+    /// A key in the message could not be decoded.
+    InvalidEncoding(Error),
+    /// A dealer broadcast two different sets of commitments.
+    Equivocation(u32),
+    /// Every dealer was disqualified.
+    NoQualifiedDealers,
+    /// Too few valid shares were revealed to reconstruct a qualified dealer's polynomial.
+    ReconstructionFailed(u32),
+}
+
+impl fmt::Display for DkgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DkgError::InvalidThreshold => {
+                write!(f, "threshold must be at least 1 and at most the number of parties")
+            }
+            DkgError::InvalidIndex(index) => write!(f, "party index {} is out of range", index),
+            DkgError::UnexpectedMessage => write!(f, "message not expected in the current round"),
+            DkgError::NotRecipient => write!(f, "message is addressed to another party"),
+            DkgError::InvalidMessage => write!(f, "invalid message encoding"),
+            DkgError::InvalidEncoding(e) => write!(f, "invalid key in message: {}", e),
+            DkgError::Equivocation(dealer) => {
+                write!(f, "dealer {} broadcast conflicting commitments", dealer)
+            }
+            DkgError::NoQualifiedDealers => write!(f, "every dealer was disqualified"),
+            DkgError::ReconstructionFailed(dealer) => {
+                write!(f, "too few valid shares to reconstruct dealer {}", dealer)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DkgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DkgError::InvalidEncoding(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for DkgError {
+    fn from(e: Error) -> Self {
+        DkgError::InvalidEncoding(e)
+    }
+}
+
+/// A message of the distributed key generation protocol.
+///
+/// `Share` messages MUST be sent over a private and authenticated channel, all other messages
+/// MUST be broadcast over an authenticated channel. `recipient` tells the transport which to use.
+///
+/// Each share is sent with its `blinding` share, the evaluation of the dealer's second
+/// polynomial which hides the secret in the Pedersen commitments.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum DkgMessage {
+    /// Round 1: Pedersen commitments to the dealer's polynomials.
+    PedersenCommitments { dealer: u32, commitments: Vec<PublicKey> },
+    /// Round 1: The dealer's shares for a single recipient.
+    Share { dealer: u32, recipient: u32, share: SecretKey, blinding: SecretKey },
+    /// Round 2: A complaint that a dealer's shares were missing or inconsistent.
+    Complaint { complainer: u32, dealer: u32 },
+    /// Round 3: A dealer publicly reveals the shares a complainer disputed.
+    Justification { dealer: u32, recipient: u32, share: SecretKey, blinding: SecretKey },
+    /// Round 4: Feldman commitments to the polynomial of a qualified dealer.
+    FeldmanCommitments { dealer: u32, commitments: Vec<PublicKey> },
+    /// Round 5: A party reveals its shares to prove a dealer's Feldman commitments are
+    /// inconsistent.
+    CommitmentComplaint { complainer: u32, dealer: u32, share: SecretKey, blinding: SecretKey },
+    /// Round 6: A party reveals its shares so a dealer's polynomial can be reconstructed.
+    Reconstruction { sender: u32, dealer: u32, share: SecretKey, blinding: SecretKey },
+}
+
+impl DkgMessage {
+    /// The recipient of a private message, or None if the message is broadcast.
+    pub fn recipient(&self) -> Option<u32> {
+        match self {
+            DkgMessage::Share { recipient, .. } => Some(*recipient),
+            _ => None,
+        }
+    }
+
+    /// The index of the party who sent the message.
+    pub fn sender(&self) -> u32 {
+        match self {
+            DkgMessage::PedersenCommitments { dealer, .. } => *dealer,
+            DkgMessage::Share { dealer, .. } => *dealer,
+            DkgMessage::Complaint { complainer, .. } => *complainer,
+            DkgMessage::Justification { dealer, .. } => *dealer,
+            DkgMessage::FeldmanCommitments { dealer, .. } => *dealer,
+            DkgMessage::CommitmentComplaint { complainer, .. } => *complainer,
+            DkgMessage::Reconstruction { sender, .. } => *sender,
+        }
+    }
+
+    /// Encode the message as bytes.
+    ///
+    /// tag || sender || (commitments | dealer | (recipient | dealer) || share || blinding)
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let tag = match self {
+            DkgMessage::PedersenCommitments { .. } => PEDERSEN_COMMITMENTS_TAG,
+            DkgMessage::Share { .. } => SHARE_TAG,
+            DkgMessage::Complaint { .. } => COMPLAINT_TAG,
+            DkgMessage::Justification { .. } => JUSTIFICATION_TAG,
+            DkgMessage::FeldmanCommitments { .. } => FELDMAN_COMMITMENTS_TAG,
+            DkgMessage::CommitmentComplaint { .. } => COMMITMENT_COMPLAINT_TAG,
+            DkgMessage::Reconstruction { .. } => RECONSTRUCTION_TAG,
+        };
+        let mut bytes = vec![tag];
+        bytes.extend_from_slice(&self.sender().to_be_bytes());
+
+        match self {
+            DkgMessage::PedersenCommitments { commitments, .. }
+            | DkgMessage::FeldmanCommitments { commitments, .. } => {
+                for commitment in commitments {
+                    bytes.extend_from_slice(&commitment.as_bytes());
+                }
+            }
+            DkgMessage::Complaint { dealer, .. } => {
+                bytes.extend_from_slice(&dealer.to_be_bytes());
+            }
+            DkgMessage::Share { recipient: index, share, blinding, .. }
+            | DkgMessage::Justification { recipient: index, share, blinding, .. }
+            | DkgMessage::CommitmentComplaint { dealer: index, share, blinding, .. }
+            | DkgMessage::Reconstruction { dealer: index, share, blinding, .. } => {
+                bytes.extend_from_slice(&index.to_be_bytes());
+                bytes.extend_from_slice(&share.as_bytes());
+                bytes.extend_from_slice(&blinding.as_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Decode a message from bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DkgError> {
+        if bytes.len() < 5 {
+            return Err(DkgError::InvalidMessage);
+        }
+        let sender = read_u32(&bytes[1..5]);
+        let body = &bytes[5..];
+
+        match bytes[0] {
+            PEDERSEN_COMMITMENTS_TAG | FELDMAN_COMMITMENTS_TAG => {
+                if body.is_empty() || body.len() % G1_BYTES != 0 {
+                    return Err(DkgError::InvalidMessage);
+                }
+                let commitments = body
+                    .chunks(G1_BYTES)
+                    .map(PublicKey::from_bytes)
+                    .collect::<Result<Vec<PublicKey>, Error>>()?;
+                if bytes[0] == PEDERSEN_COMMITMENTS_TAG {
+                    Ok(DkgMessage::PedersenCommitments { dealer: sender, commitments })
+                } else {
+                    Ok(DkgMessage::FeldmanCommitments { dealer: sender, commitments })
+                }
+            }
+            COMPLAINT_TAG => {
+                if body.len() != 4 {
+                    return Err(DkgError::InvalidMessage);
+                }
+                Ok(DkgMessage::Complaint { complainer: sender, dealer: read_u32(body) })
+            }
+            SHARE_TAG | JUSTIFICATION_TAG | COMMITMENT_COMPLAINT_TAG | RECONSTRUCTION_TAG => {
+                if body.len() != 4 + 2 * SECRET_KEY_BYTES {
+                    return Err(DkgError::InvalidMessage);
+                }
+                let index = read_u32(&body[..4]);
+                let share = SecretKey::from_bytes(&body[4..4 + SECRET_KEY_BYTES])?;
+                let blinding = SecretKey::from_bytes(&body[4 + SECRET_KEY_BYTES..])?;
+                Ok(match bytes[0] {
+                    SHARE_TAG => {
+                        DkgMessage::Share { dealer: sender, recipient: index, share, blinding }
+                    }
+                    JUSTIFICATION_TAG => DkgMessage::Justification {
+                        dealer: sender,
+                        recipient: index,
+                        share,
+                        blinding,
+                    },
+                    COMMITMENT_COMPLAINT_TAG => DkgMessage::CommitmentComplaint {
+                        complainer: sender,
+                        dealer: index,
+                        share,
+                        blinding,
+                    },
+                    _ => DkgMessage::Reconstruction { sender, dealer: index, share, blinding },
+                })
+            }
+            _ => Err(DkgError::InvalidMessage),
+        }
+    }
+}
+
+/// The result of a successful distributed key generation for a single party.
+#[derive(Clone)]
+pub struct DkgOutput {
+    /// The index of this party.
+    pub index: u32,
+    /// This party's share of the group SecretKey, which no party ever learns.
+    pub secret_share: SecretKey,
+    /// The group PublicKey.
+    pub public_key: PublicKey,
+    /// The PublicKey shares of every party, used to verify partial Signatures.
+    pub public_key_shares: Vec<(u32, PublicKey)>,
+    /// Feldman commitments to the group polynomial, the first is the group PublicKey.
+    pub commitments: Vec<PublicKey>,
+    /// The indices of the dealers whose polynomials make up the group SecretKey.
+    pub qualified: Vec<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Round {
+    Start,
+    Dealt,
+    Complained,
+    Justified,
+    Committed,
+    CommitmentsComplained,
+    Reconstructed,
+    Finished,
+}
+
+// A share of a dealer's polynomial and the matching share of its blinding polynomial.
+type SharePair = (SecretKey, SecretKey);
+
+/// A single party of the Gennaro et al. distributed key generation.
+///
+/// Every party acts as a dealer of a random polynomial. The protocol has two phases:
+///
+/// 1. The polynomial is shared with Pedersen verifiable secret sharing. Pedersen commitments
+///    reveal nothing about the dealer's secret, so the set of qualified dealers is decided before
+///    anything about the group PublicKey is public. A dealer that cannot justify a disputed share
+///    or receives `threshold` or more complaints is disqualified.
+/// 2. Each qualified dealer publishes Feldman commitments to its polynomial. If these are missing
+///    or inconsistent with a share, the parties publicly reveal their shares of that dealer and
+///    reconstruct its polynomial, so a qualified dealer cannot withdraw its contribution.
+///
+/// The group SecretKey is the sum of the qualified dealers' secrets and is never held by any
+/// party. Provided fewer than `threshold` parties are dishonest and `n >= 2 * threshold - 1`, the
+/// group PublicKey is uniformly random.
+///
+/// The state machine is transport agnostic, each round returns the messages to send and
+/// `handle_message` accepts the messages of other parties:
+/// 1. `deal`: broadcast Pedersen commitments and privately send shares.
+/// 2. `complain`: broadcast complaints against dealers with missing or invalid shares.
+/// 3. `justify`: broadcast the disputed shares of complaints against this party.
+/// 4. `commit`: decide the qualified dealers and broadcast Feldman commitments if qualified.
+/// 5. `complain_commitments`: reveal this party's shares of dealers whose Feldman commitments
+///    are inconsistent with them.
+/// 6. `reconstruct`: reveal this party's shares of dealers whose Feldman commitments are
+///    missing or were proven inconsistent.
+/// 7. `finalize`: reconstruct the exposed dealers' commitments and output the key shares.
+///
+/// https://link.springer.com/article/10.1007/s00145-006-0347-3
+pub struct Dkg {
+    index: u32,
+    threshold: usize,
+    n: u32,
+    round: Round,
+    pedersen_generator: GroupG1,
+    polynomial: Option<Polynomial>,
+    blinding_polynomial: Option<Polynomial>,
+    pedersen_commitments: BTreeMap<u32, Vec<PublicKey>>,
+    shares: BTreeMap<u32, SharePair>,
+    complaints: BTreeMap<u32, BTreeSet<u32>>,
+    justified: BTreeMap<u32, BTreeSet<u32>>,
+    disqualified: BTreeSet<u32>,
+    qualified: Vec<u32>,
+    feldman_commitments: BTreeMap<u32, Vec<PublicKey>>,
+    commitment_complaints: BTreeMap<u32, BTreeMap<u32, SharePair>>,
+    exposed: BTreeSet<u32>,
+    reconstruction_shares: BTreeMap<u32, BTreeMap<u32, SharePair>>,
+}
+
+impl Dkg {
+    /// Instantiate the party at `index` in [1, n] of a DKG for a `threshold` of `n` key.
+    pub fn new(index: u32, threshold: usize, n: u32) -> Result<Self, DkgError> {
+        if threshold == 0 || threshold > n as usize {
+            return Err(DkgError::InvalidThreshold);
+        }
+        if index == 0 || index > n {
+            return Err(DkgError::InvalidIndex(index));
+        }
+
+        Ok(Self {
+            index,
+            threshold,
+            n,
+            round: Round::Start,
+            pedersen_generator: hash_to_curve_g1_with_dst(
+                PEDERSEN_GENERATOR_MESSAGE,
+                PEDERSEN_GENERATOR_DST,
+            ),
+            polynomial: None,
+            blinding_polynomial: None,
+            pedersen_commitments: BTreeMap::new(),
+            shares: BTreeMap::new(),
+            complaints: BTreeMap::new(),
+            justified: BTreeMap::new(),
+            disqualified: BTreeSet::new(),
+            qualified: Vec::new(),
+            feldman_commitments: BTreeMap::new(),
+            commitment_complaints: BTreeMap::new(),
+            exposed: BTreeSet::new(),
+            reconstruction_shares: BTreeMap::new(),
+        })
+    }
+
+    /// The index of this party.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Round 1: Generate random polynomials, returning their Pedersen commitments and the shares
+    /// for each party.
+    pub fn deal<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<Vec<DkgMessage>, DkgError> {
+        if self.round != Round::Start {
+            return Err(DkgError::UnexpectedMessage);
+        }
+        self.round = Round::Dealt;
+
+        let polynomial = Polynomial::random(&SecretKey::random(rng), self.threshold, rng);
+        let blinding_polynomial = Polynomial::random(&SecretKey::random(rng), self.threshold, rng);
+        let commitments =
+            polynomial.pedersen_commitments(&blinding_polynomial, &self.pedersen_generator);
+        self.pedersen_commitments.insert(self.index, commitments.clone());
+
+        let mut messages =
+            vec![DkgMessage::PedersenCommitments { dealer: self.index, commitments }];
+        let shares = polynomial.shares(self.n as usize);
+        let blindings = blinding_polynomial.shares(self.n as usize);
+        for ((recipient, share), (_, blinding)) in shares.into_iter().zip(blindings) {
+            if recipient == self.index {
+                self.shares.insert(self.index, (share, blinding));
+            } else {
+                messages.push(DkgMessage::Share { dealer: self.index, recipient, share, blinding });
+            }
+        }
+        self.polynomial = Some(polynomial);
+        self.blinding_polynomial = Some(blinding_polynomial);
+        Ok(messages)
+    }
+
+    /// Process a message from another party.
+    ///
+    /// Messages of a round are accepted until this party moves past that round.
+    pub fn handle_message(&mut self, message: &DkgMessage) -> Result<(), DkgError> {
+        self.check_index(message.sender())?;
+
+        match message {
+            DkgMessage::PedersenCommitments { dealer, commitments } => {
+                self.check_round(Round::Dealt)?;
+                if let Some(existing) = self.pedersen_commitments.get(dealer) {
+                    if existing != commitments {
+                        self.disqualified.insert(*dealer);
+                        return Err(DkgError::Equivocation(*dealer));
+                    }
+                    return Ok(());
+                }
+                if commitments.len() != self.threshold {
+                    self.disqualified.insert(*dealer);
+                }
+                self.pedersen_commitments.insert(*dealer, commitments.clone());
+            }
+            DkgMessage::Share { dealer, recipient, share, blinding } => {
+                self.check_round(Round::Dealt)?;
+                if *recipient != self.index {
+                    return Err(DkgError::NotRecipient);
+                }
+                self.shares.insert(*dealer, (share.clone(), blinding.clone()));
+            }
+            DkgMessage::Complaint { complainer, dealer } => {
+                self.check_round(Round::Complained)?;
+                self.check_index(*dealer)?;
+                self.complaints.entry(*dealer).or_default().insert(*complainer);
+            }
+            DkgMessage::Justification { dealer, recipient, share, blinding } => {
+                self.check_round(Round::Justified)?;
+                self.check_index(*recipient)?;
+                if !self.verify_pedersen_share(*dealer, *recipient, share, blinding) {
+                    self.disqualified.insert(*dealer);
+                    return Ok(());
+                }
+
+                self.justified.entry(*dealer).or_default().insert(*recipient);
+                if *recipient == self.index {
+                    self.shares.insert(*dealer, (share.clone(), blinding.clone()));
+                }
+            }
+            DkgMessage::FeldmanCommitments { dealer, commitments } => {
+                self.check_round(Round::Committed)?;
+                if let Some(existing) = self.feldman_commitments.get(dealer) {
+                    if existing != commitments {
+                        // A qualified dealer is reconstructed rather than disqualified
+                        self.exposed.insert(*dealer);
+                        return Err(DkgError::Equivocation(*dealer));
+                    }
+                    return Ok(());
+                }
+                self.feldman_commitments.insert(*dealer, commitments.clone());
+            }
+            DkgMessage::CommitmentComplaint { complainer, dealer, share, blinding } => {
+                self.check_round(Round::CommitmentsComplained)?;
+                self.check_index(*dealer)?;
+                self.commitment_complaints
+                    .entry(*dealer)
+                    .or_default()
+                    .insert(*complainer, (share.clone(), blinding.clone()));
+            }
+            DkgMessage::Reconstruction { sender, dealer, share, blinding } => {
+                self.check_round(Round::Reconstructed)?;
+                self.check_index(*dealer)?;
+                self.reconstruction_shares
+                    .entry(*dealer)
+                    .or_default()
+                    .insert(*sender, (share.clone(), blinding.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Round 2: Complain about every dealer whose shares are missing or inconsistent.
+    ///
+    /// Dealers who did not broadcast valid commitments are disqualified.
+    pub fn complain(&mut self) -> Result<Vec<DkgMessage>, DkgError> {
+        if self.round != Round::Dealt {
+            return Err(DkgError::UnexpectedMessage);
+        }
+        self.round = Round::Complained;
+
+        let mut messages = Vec::new();
+        for dealer in 1..=self.n {
+            if !self.pedersen_commitments.contains_key(&dealer) {
+                self.disqualified.insert(dealer);
+                continue;
+            }
+            let valid = self.shares.get(&dealer).is_some_and(|(share, blinding)| {
+                self.verify_pedersen_share(dealer, self.index, share, blinding)
+            });
+            if !valid {
+                self.shares.remove(&dealer);
+                self.complaints.entry(dealer).or_default().insert(self.index);
+                messages.push(DkgMessage::Complaint { complainer: self.index, dealer });
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Round 3: Reveal the shares of each party who complained about this dealer.
+    pub fn justify(&mut self) -> Result<Vec<DkgMessage>, DkgError> {
+        if self.round != Round::Complained {
+            return Err(DkgError::UnexpectedMessage);
+        }
+        self.round = Round::Justified;
+
+        let (polynomial, blinding_polynomial, complainers) =
+            match (&self.polynomial, &self.blinding_polynomial, self.complaints.get(&self.index)) {
+                (Some(polynomial), Some(blinding_polynomial), Some(complainers)) => {
+                    (polynomial, blinding_polynomial, complainers)
+                }
+                _ => return Ok(Vec::new()),
+            };
+        let messages = complainers
+            .iter()
+            .map(|recipient| DkgMessage::Justification {
+                dealer: self.index,
+                recipient: *recipient,
+                share: SecretKey::from_raw(polynomial.evaluate(*recipient)),
+                blinding: SecretKey::from_raw(blinding_polynomial.evaluate(*recipient)),
+            })
+            .collect();
+        self.justified.insert(self.index, complainers.clone());
+        Ok(messages)
+    }
+
+    /// Round 4: Disqualify dealers who failed to justify complaints and, if this dealer is
+    /// qualified, broadcast Feldman commitments to its polynomial.
+    ///
+    /// A dealer with `threshold` or more complaints is disqualified as its justifications
+    /// would reveal its secret.
+    pub fn commit(&mut self) -> Result<Vec<DkgMessage>, DkgError> {
+        if self.round != Round::Justified {
+            return Err(DkgError::UnexpectedMessage);
+        }
+        self.round = Round::Committed;
+        let polynomial = self.polynomial.take();
+        self.blinding_polynomial = None;
+
+        for (dealer, complainers) in self.complaints.iter() {
+            let justified = self.justified.get(dealer);
+            if complainers.len() >= self.threshold
+                || complainers.iter().any(|c| !justified.is_some_and(|j| j.contains(c)))
+            {
+                self.disqualified.insert(*dealer);
+            }
+        }
+
+        self.qualified =
+            (1..=self.n).filter(|dealer| !self.disqualified.contains(dealer)).collect();
+        if self.qualified.is_empty() {
+            return Err(DkgError::NoQualifiedDealers);
+        }
+
+        match polynomial {
+            Some(polynomial) if self.qualified.contains(&self.index) => {
+                let commitments = polynomial.commitments();
+                self.feldman_commitments.insert(self.index, commitments.clone());
+                Ok(vec![DkgMessage::FeldmanCommitments { dealer: self.index, commitments }])
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Round 5: Reveal this party's shares of each qualified dealer whose Feldman commitments
+    /// are inconsistent with them.
+    pub fn complain_commitments(&mut self) -> Result<Vec<DkgMessage>, DkgError> {
+        if self.round != Round::Committed {
+            return Err(DkgError::UnexpectedMessage);
+        }
+        self.round = Round::CommitmentsComplained;
+
+        let mut messages = Vec::new();
+        for dealer in self.qualified.iter() {
+            let commitments = match self.feldman_commitments.get(dealer) {
+                Some(commitments) if commitments.len() == self.threshold => commitments,
+                // Missing commitments are reconstructed without a complaint
+                _ => continue,
+            };
+            // Every qualified dealer has valid shares, as complaints must be justified
+            let (share, blinding) = &self.shares[dealer];
+            if !verify_share(self.index, share, commitments) {
+                messages.push(DkgMessage::CommitmentComplaint {
+                    complainer: self.index,
+                    dealer: *dealer,
+                    share: share.clone(),
+                    blinding: blinding.clone(),
+                });
+                self.commitment_complaints
+                    .entry(*dealer)
+                    .or_default()
+                    .insert(self.index, (share.clone(), blinding.clone()));
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Round 6: Reveal this party's shares of each qualified dealer whose Feldman commitments
+    /// are missing or were proven inconsistent by a complaint.
+    ///
+    /// A complaint is valid if the revealed shares match the dealer's Pedersen commitments
+    /// but not its Feldman commitments.
+    pub fn reconstruct(&mut self) -> Result<Vec<DkgMessage>, DkgError> {
+        if self.round != Round::CommitmentsComplained {
+            return Err(DkgError::UnexpectedMessage);
+        }
+        self.round = Round::Reconstructed;
+
+        for dealer in self.qualified.iter() {
+            let exposed = match self.feldman_commitments.get(dealer) {
+                Some(commitments) if commitments.len() == self.threshold => {
+                    self.commitment_complaints.get(dealer).is_some_and(|complaints| {
+                        complaints.iter().any(|(complainer, (share, blinding))| {
+                            self.verify_pedersen_share(*dealer, *complainer, share, blinding)
+                                && !verify_share(*complainer, share, commitments)
+                        })
+                    })
+                }
+                _ => true,
+            };
+            if exposed {
+                self.exposed.insert(*dealer);
+            }
+        }
+        // Equivocating dealers which were disqualified in phase 1 need not be reconstructed
+        let qualified = &self.qualified;
+        self.exposed.retain(|dealer| qualified.contains(dealer));
+
+        let mut messages = Vec::new();
+        for dealer in self.exposed.iter() {
+            let (share, blinding) = &self.shares[dealer];
+            messages.push(DkgMessage::Reconstruction {
+                sender: self.index,
+                dealer: *dealer,
+                share: share.clone(),
+                blinding: blinding.clone(),
+            });
+            self.reconstruction_shares
+                .entry(*dealer)
+                .or_default()
+                .insert(self.index, (share.clone(), blinding.clone()));
+        }
+        Ok(messages)
+    }
+
+    /// Reconstruct the commitments of exposed dealers and output this party's key share.
+    pub fn finalize(&mut self) -> Result<DkgOutput, DkgError> {
+        if self.round != Round::Reconstructed {
+            return Err(DkgError::UnexpectedMessage);
+        }
+        self.round = Round::Finished;
+
+        // x_j = sum(s_ij) and C_k = sum(A_ik) over the qualified dealers i
+        let r = Big::new_ints(&CURVE_ORDER);
+        let mut secret_share = Big::new();
+        let mut commitments = vec![GroupG1::new(); self.threshold];
+        for dealer in self.qualified.iter() {
+            let (share, _) = &self.shares[dealer];
+            secret_share.add(share.as_raw());
+            secret_share.rmod(&r);
+
+            let reconstructed;
+            let dealer_commitments = if self.exposed.contains(dealer) {
+                reconstructed = self.reconstruct_commitments(*dealer)?;
+                &reconstructed
+            } else {
+                &self.feldman_commitments[dealer]
+            };
+            for (commitment, dealer_commitment) in commitments.iter_mut().zip(dealer_commitments) {
+                commitment.add(&dealer_commitment.point);
+            }
+        }
+        let commitments: Vec<PublicKey> =
+            commitments.into_iter().map(|point| PublicKey { point }).collect();
+
+        let public_key_shares = (1..=self.n)
+            .map(|index| (index, public_key_share(index, &commitments).unwrap()))
+            .collect();
+
+        Ok(DkgOutput {
+            index: self.index,
+            secret_share: SecretKey::from_raw(secret_share),
+            public_key: commitments[0].clone(),
+            public_key_shares,
+            commitments,
+            qualified: self.qualified.clone(),
+        })
+    }
+
+    // Interpolate a dealer's polynomial from `threshold` revealed shares which match its
+    // Pedersen commitments, returning Feldman commitments to it.
+    fn reconstruct_commitments(&self, dealer: u32) -> Result<Vec<PublicKey>, DkgError> {
+        let revealed = self
+            .reconstruction_shares
+            .get(&dealer)
+            .into_iter()
+            .chain(self.commitment_complaints.get(&dealer))
+            .flatten();
+
+        let mut points: Vec<(u32, Big)> = Vec::with_capacity(self.threshold);
+        for (index, (share, blinding)) in revealed {
+            if points.len() == self.threshold {
+                break;
+            }
+            if !points.iter().any(|(x, _)| x == index)
+                && self.verify_pedersen_share(dealer, *index, share, blinding)
+            {
+                points.push((*index, share.as_raw().clone()));
+            }
+        }
+        if points.len() < self.threshold {
+            return Err(DkgError::ReconstructionFailed(dealer));
+        }
+
+        let polynomial =
+            Polynomial::interpolate(&points).map_err(|_| DkgError::ReconstructionFailed(dealer))?;
+        Ok(polynomial.commitments())
+    }
+
+    // Verify shares at `index` against a dealer's Pedersen commitments.
+    //
+    // share * G1 + blinding * H == sum(C_k * index^k)
+    fn verify_pedersen_share(
+        &self,
+        dealer: u32,
+        index: u32,
+        share: &SecretKey,
+        blinding: &SecretKey,
+    ) -> bool {
+        let commitments = match self.pedersen_commitments.get(&dealer) {
+            Some(commitments) if commitments.len() == self.threshold => commitments,
+            _ => return false,
+        };
+        match public_key_share(index, commitments) {
+            Ok(expected) => {
+                let mut point = PublicKey::from_secret_key(share).point;
+                point.add(&g1mul(&self.pedersen_generator, blinding.as_raw()));
+                point.equals(&expected.point)
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn check_index(&self, index: u32) -> Result<(), DkgError> {
+        if index == 0 || index > self.n {
+            return Err(DkgError::InvalidIndex(index));
+        }
+        Ok(())
+    }
+
+    fn check_round(&self, round: Round) -> Result<(), DkgError> {
+        if self.round > round {
+            return Err(DkgError::UnexpectedMessage);
+        }
+        Ok(())
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    let mut int = [0u8; 4];
+    int.copy_from_slice(&bytes[..4]);
+    u32::from_be_bytes(int)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::signature::Signature;
+    use super::*;
+
+    // Send each message over the wire to every party it is addressed to
+    fn deliver(parties: &mut [Dkg], messages: &[DkgMessage]) {
+        for message in messages {
+            let message = DkgMessage::from_bytes(&message.as_bytes()).unwrap();
+            for party in parties.iter_mut() {
+                let addressed = message.recipient().map_or(true, |r| r == party.index());
+                if party.index() != message.sender() && addressed {
+                    party.handle_message(&message).unwrap();
+                }
+            }
+        }
+    }
+
+    fn new_parties(threshold: usize, n: u32) -> Vec<Dkg> {
+        (1..=n).map(|index| Dkg::new(index, threshold, n).unwrap()).collect()
+    }
+
+    fn deal(parties: &mut [Dkg]) -> Vec<DkgMessage> {
+        let mut rng = rand::thread_rng();
+        parties.iter_mut().flat_map(|party| party.deal(&mut rng).unwrap()).collect()
+    }
+
+    fn complain(parties: &mut [Dkg]) -> Vec<DkgMessage> {
+        parties.iter_mut().flat_map(|party| party.complain().unwrap()).collect()
+    }
+
+    fn justify(parties: &mut [Dkg]) -> Vec<DkgMessage> {
+        parties.iter_mut().flat_map(|party| party.justify().unwrap()).collect()
+    }
+
+    fn commit(parties: &mut [Dkg]) -> Vec<DkgMessage> {
+        parties.iter_mut().flat_map(|party| party.commit().unwrap()).collect()
+    }
+
+    fn complain_commitments(parties: &mut [Dkg]) -> Vec<DkgMessage> {
+        parties.iter_mut().flat_map(|party| party.complain_commitments().unwrap()).collect()
+    }
+
+    fn reconstruct(parties: &mut [Dkg]) -> Vec<DkgMessage> {
+        parties.iter_mut().flat_map(|party| party.reconstruct().unwrap()).collect()
+    }
+
+    // Run phase 1 without any misbehaviour
+    fn run_phase_1(parties: &mut [Dkg]) {
+        let messages = deal(parties);
+        deliver(parties, &messages);
+        let messages = complain(parties);
+        deliver(parties, &messages);
+        let messages = justify(parties);
+        deliver(parties, &messages);
+    }
+
+    // Run phase 2 without any misbehaviour and output each party's key share
+    fn finalize(parties: &mut [Dkg]) -> Vec<DkgOutput> {
+        let messages = commit(parties);
+        deliver(parties, &messages);
+        let messages = complain_commitments(parties);
+        deliver(parties, &messages);
+        let messages = reconstruct(parties);
+        deliver(parties, &messages);
+        parties.iter_mut().map(|party| party.finalize().unwrap()).collect()
+    }
+
+    // Replace the share a dealer sends to a recipient with an inconsistent one
+    fn tamper_share(messages: &mut [DkgMessage], from: u32, to: u32) {
+        for message in messages.iter_mut() {
+            if let DkgMessage::Share { dealer, recipient, share, .. } = message {
+                if *dealer == from && *recipient == to {
+                    *share = SecretKey::random(&mut rand::thread_rng());
+                }
+            }
+        }
+    }
+
+    // All parties agree on the group key and any `threshold` shares can sign for it
+    fn check_outputs(outputs: &[DkgOutput], threshold: usize, qualified: &[u32]) {
+        let public_key = &outputs[0].public_key;
+        for output in outputs {
+            assert_eq!(output.qualified, qualified);
+            assert_eq!(&output.public_key, public_key);
+            assert_eq!(output.commitments, outputs[0].commitments);
+            assert_eq!(output.public_key_shares, outputs[0].public_key_shares);
+            assert!(verify_share(output.index, &output.secret_share, &output.commitments));
+            assert_eq!(
+                output.public_key_shares[output.index as usize - 1],
+                (output.index, PublicKey::from_secret_key(&output.secret_share))
+            );
+        }
+
+        let msg = b"distributed";
+        for signers in outputs.windows(threshold) {
+            let partial_signatures: Vec<(u32, Signature)> = signers
+                .iter()
+                .map(|output| (output.index, Signature::new(msg, &output.secret_share)))
+                .collect();
+            let signature = Signature::combine_threshold(&partial_signatures).unwrap();
+            assert!(signature.verify(msg, public_key));
+        }
+
+        let partial_signatures: Vec<(u32, Signature)> = outputs[..threshold - 1]
+            .iter()
+            .map(|output| (output.index, Signature::new(msg, &output.secret_share)))
+            .collect();
+        let signature = Signature::combine_threshold(&partial_signatures).unwrap();
+        assert!(!signature.verify(msg, public_key));
+    }
+
+    #[test]
+    fn test_dkg_honest() {
+        let mut parties = new_parties(3, 5);
+
+        let messages = deal(&mut parties);
+        deliver(&mut parties, &messages);
+        let messages = complain(&mut parties);
+        assert!(messages.is_empty());
+        let messages = justify(&mut parties);
+        assert!(messages.is_empty());
+
+        let messages = commit(&mut parties);
+        assert_eq!(messages.len(), 5);
+        deliver(&mut parties, &messages);
+        let messages = complain_commitments(&mut parties);
+        assert!(messages.is_empty());
+        let messages = reconstruct(&mut parties);
+        assert!(messages.is_empty());
+
+        let outputs: Vec<DkgOutput> =
+            parties.iter_mut().map(|party| party.finalize().unwrap()).collect();
+        check_outputs(&outputs, 3, &[1, 2, 3, 4, 5]);
+        assert_eq!(
+            PublicKey::combine_threshold(&outputs[0].public_key_shares).unwrap(),
+            outputs[0].public_key
+        );
+    }
+
+    #[test]
+    fn test_dkg_pedersen_commitments_hide_secret() {
+        let mut parties = new_parties(2, 3);
+        let dealt = deal(&mut parties);
+        deliver(&mut parties, &dealt);
+        let messages = complain(&mut parties);
+        deliver(&mut parties, &messages);
+        let messages = justify(&mut parties);
+        deliver(&mut parties, &messages);
+        let outputs = finalize(&mut parties);
+
+        // The group PublicKey is not the sum of the phase 1 commitments
+        let mut sum = GroupG1::new();
+        for message in dealt.iter() {
+            if let DkgMessage::PedersenCommitments { commitments, .. } = message {
+                sum.add(&commitments[0].point);
+            }
+        }
+        assert!(!sum.equals(&outputs[0].public_key.point));
+        check_outputs(&outputs, 2, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_dkg_justified_complaint() {
+        let mut parties = new_parties(3, 5);
+
+        // Dealer 2 sends party 4 an invalid share but reveals the valid shares when challenged
+        let mut messages = deal(&mut parties);
+        tamper_share(&mut messages, 2, 4);
+        deliver(&mut parties, &messages);
+
+        let messages = complain(&mut parties);
+        assert_eq!(messages, vec![DkgMessage::Complaint { complainer: 4, dealer: 2 }]);
+        deliver(&mut parties, &messages);
+
+        let messages = justify(&mut parties);
+        assert_eq!(messages.len(), 1);
+        deliver(&mut parties, &messages);
+
+        let outputs = finalize(&mut parties);
+        check_outputs(&outputs, 3, &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_dkg_unjustified_complaint() {
+        let mut parties = new_parties(3, 5);
+
+        // Dealer 2 sends party 4 an invalid share and never justifies it
+        let mut messages = deal(&mut parties);
+        tamper_share(&mut messages, 2, 4);
+        deliver(&mut parties, &messages);
+
+        let messages = complain(&mut parties);
+        deliver(&mut parties, &messages);
+
+        let messages = justify(&mut parties);
+        assert_eq!(messages[0].sender(), 2);
+
+        // The honest parties all disqualify dealer 2
+        parties.remove(1);
+        let outputs = finalize(&mut parties);
+        check_outputs(&outputs, 3, &[1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_dkg_invalid_justification() {
+        let mut parties = new_parties(2, 4);
+
+        // Dealer 1 sends party 3 an invalid share and justifies it with another invalid share
+        let mut messages = deal(&mut parties);
+        tamper_share(&mut messages, 1, 3);
+        deliver(&mut parties, &messages);
+
+        let messages = complain(&mut parties);
+        deliver(&mut parties, &messages);
+
+        let mut messages = justify(&mut parties);
+        if let DkgMessage::Justification { share, .. } = &mut messages[0] {
+            *share = SecretKey::random(&mut rand::thread_rng());
+        }
+        deliver(&mut parties, &messages);
+
+        let outputs = finalize(&mut parties[1..]);
+        check_outputs(&outputs, 2, &[2, 3, 4]);
+    }
+
+    #[test]
+    fn test_dkg_false_complaint() {
+        let mut parties = new_parties(3, 4);
+
+        let messages = deal(&mut parties);
+        deliver(&mut parties, &messages);
+
+        // Party 4 falsely complains about the honest dealer 1
+        let mut messages = complain(&mut parties);
+        messages.push(DkgMessage::Complaint { complainer: 4, dealer: 1 });
+        deliver(&mut parties, &messages);
+
+        let messages = justify(&mut parties);
+        assert_eq!(messages.len(), 1);
+        deliver(&mut parties, &messages);
+
+        let outputs = finalize(&mut parties);
+        check_outputs(&outputs, 3, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_dkg_too_many_complaints() {
+        let mut parties = new_parties(2, 4);
+
+        let messages = deal(&mut parties);
+        deliver(&mut parties, &messages);
+
+        // Justifying `threshold` complaints would reveal dealer 1's secret
+        let messages = [
+            DkgMessage::Complaint { complainer: 3, dealer: 1 },
+            DkgMessage::Complaint { complainer: 4, dealer: 1 },
+        ];
+        complain(&mut parties);
+        for party in parties.iter_mut() {
+            for message in messages.iter() {
+                party.handle_message(message).unwrap();
+            }
+        }
+        let messages = justify(&mut parties);
+        assert_eq!(messages.len(), 2);
+        deliver(&mut parties, &messages);
+
+        let outputs = finalize(&mut parties);
+        check_outputs(&outputs, 2, &[2, 3, 4]);
+    }
+
+    #[test]
+    fn test_dkg_missing_dealer() {
+        let mut parties = new_parties(2, 4);
+
+        // Dealer 3 never sends any round 1 messages
+        let mut messages = deal(&mut parties);
+        messages.retain(|message| message.sender() != 3);
+        deliver(&mut parties, &messages);
+
+        let messages = complain(&mut parties);
+        assert!(messages.is_empty());
+        let messages = justify(&mut parties);
+        deliver(&mut parties, &messages);
+
+        let outputs = finalize(&mut parties);
+        check_outputs(&outputs[..2], 2, &[1, 2, 4]);
+        check_outputs(&outputs[3..], 2, &[1, 2, 4]);
+    }
+
+    #[test]
+    fn test_dkg_invalid_commitments() {
+        let mut parties = new_parties(3, 4);
+
+        // Dealer 1 commits to a polynomial of the wrong degree
+        let mut messages = deal(&mut parties);
+        if let DkgMessage::PedersenCommitments { commitments, .. } = &mut messages[0] {
+            commitments.pop();
+        }
+        deliver(&mut parties, &messages);
+        let messages = complain(&mut parties);
+        deliver(&mut parties, &messages);
+        let messages = justify(&mut parties);
+        deliver(&mut parties, &messages);
+
+        let outputs = finalize(&mut parties[1..]);
+        check_outputs(&outputs, 3, &[2, 3, 4]);
+    }
+
+    #[test]
+    fn test_dkg_inconsistent_feldman_commitments() {
+        let mut parties = new_parties(3, 5);
+        run_phase_1(&mut parties);
+
+        // Qualified dealer 2 broadcasts Feldman commitments to a different polynomial
+        let mut messages = commit(&mut parties);
+        let sk = SecretKey::random(&mut rand::thread_rng());
+        if let DkgMessage::FeldmanCommitments { commitments, .. } = &mut messages[1] {
+            commitments[1] = PublicKey::from_secret_key(&sk);
+        }
+        parties.remove(1);
+        deliver(&mut parties, &messages);
+
+        // Every honest party proves the commitments are inconsistent with its shares
+        let messages = complain_commitments(&mut parties);
+        assert_eq!(messages.len(), 4);
+        assert!(messages.iter().all(|message| match message {
+            DkgMessage::CommitmentComplaint { dealer, .. } => *dealer == 2,
+            _ => false,
+        }));
+        deliver(&mut parties, &messages);
+
+        // Dealer 2 is reconstructed rather than disqualified
+        let messages = reconstruct(&mut parties);
+        assert_eq!(messages.len(), 4);
+        deliver(&mut parties, &messages);
+
+        let outputs: Vec<DkgOutput> =
+            parties.iter_mut().map(|party| party.finalize().unwrap()).collect();
+        check_outputs(&outputs, 3, &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_dkg_missing_feldman_commitments() {
+        let mut parties = new_parties(2, 4);
+        run_phase_1(&mut parties);
+
+        // Qualified dealer 3 withholds its Feldman commitments after seeing the others
+        let mut messages = commit(&mut parties);
+        messages.retain(|message| message.sender() != 3);
+        parties.remove(2);
+        deliver(&mut parties, &messages);
+
+        let messages = complain_commitments(&mut parties);
+        assert!(messages.is_empty());
+        let messages = reconstruct(&mut parties);
+        assert_eq!(messages.len(), 3);
+        deliver(&mut parties, &messages);
+
+        let outputs: Vec<DkgOutput> =
+            parties.iter_mut().map(|party| party.finalize().unwrap()).collect();
+        check_outputs(&outputs, 2, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_dkg_false_commitment_complaint() {
+        let mut parties = new_parties(2, 3);
+        run_phase_1(&mut parties);
+        let messages = commit(&mut parties);
+        deliver(&mut parties, &messages);
+
+        // Party 3 complains about dealer 1 with invalid shares and with its valid shares
+        let mut rng = rand::thread_rng();
+        let (share, blinding) = parties[2].shares[&1].clone();
+        let mut messages = complain_commitments(&mut parties);
+        messages.push(DkgMessage::CommitmentComplaint {
+            complainer: 3,
+            dealer: 1,
+            share: SecretKey::random(&mut rng),
+            blinding: SecretKey::random(&mut rng),
+        });
+        messages.push(DkgMessage::CommitmentComplaint {
+            complainer: 3,
+            dealer: 2,
+            share,
+            blinding,
+        });
+        deliver(&mut parties, &messages);
+
+        let messages = reconstruct(&mut parties);
+        assert!(messages.is_empty());
+
+        let outputs: Vec<DkgOutput> =
+            parties.iter_mut().map(|party| party.finalize().unwrap()).collect();
+        check_outputs(&outputs, 2, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_dkg_reconstruction_failed() {
+        let mut parties = new_parties(3, 4);
+        run_phase_1(&mut parties);
+
+        // Dealer 1 withholds its Feldman commitments, party 2 receives no revealed shares
+        let mut messages = commit(&mut parties);
+        messages.retain(|message| message.sender() != 1);
+        deliver(&mut parties[1..], &messages);
+        complain_commitments(&mut parties[1..]);
+        let messages = reconstruct(&mut parties[1..]);
+        assert_eq!(messages.len(), 3);
+        deliver(&mut parties[2..], &messages);
+
+        assert_eq!(parties[1].finalize().err(), Some(DkgError::ReconstructionFailed(1)));
+        assert!(parties[2].finalize().is_ok());
+    }
+
+    #[test]
+    fn test_dkg_equivocation() {
+        let mut parties = new_parties(2, 3);
+        let messages = deal(&mut parties);
+
+        let mut rng = rand::thread_rng();
+        let commitments = vec![PublicKey::from_secret_key(&SecretKey::random(&mut rng)); 2];
+        let equivocation = DkgMessage::PedersenCommitments { dealer: 1, commitments };
+        parties[1].handle_message(&messages[0]).unwrap();
+        parties[1].handle_message(&messages[0]).unwrap();
+        assert_eq!(parties[1].handle_message(&equivocation), Err(DkgError::Equivocation(1)));
+    }
+
+    #[test]
+    fn test_dkg_feldman_equivocation() {
+        let mut parties = new_parties(2, 3);
+        run_phase_1(&mut parties);
+        let messages = commit(&mut parties);
+
+        // Equivocating dealer 1 is reconstructed by party 2
+        let mut rng = rand::thread_rng();
+        let commitments = vec![PublicKey::from_secret_key(&SecretKey::random(&mut rng)); 2];
+        let equivocation = DkgMessage::FeldmanCommitments { dealer: 1, commitments };
+        parties[1].handle_message(&messages[0]).unwrap();
+        assert_eq!(parties[1].handle_message(&equivocation), Err(DkgError::Equivocation(1)));
+        deliver(&mut parties, &messages[1..]);
+        complain_commitments(&mut parties);
+        assert_eq!(parties[1].reconstruct().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_dkg_no_qualified_dealers() {
+        let mut rng = rand::thread_rng();
+        let mut party = Dkg::new(1, 1, 2).unwrap();
+        party.deal(&mut rng).unwrap();
+        let commitments = vec![PublicKey::from_secret_key(&SecretKey::random(&mut rng)); 2];
+        party.handle_message(&DkgMessage::PedersenCommitments { dealer: 2, commitments }).unwrap();
+        party.complain().unwrap();
+
+        // Dealer 1 cannot justify a share it was never asked about
+        let share = SecretKey::random(&mut rng);
+        let blinding = SecretKey::random(&mut rng);
+        party
+            .handle_message(&DkgMessage::Justification { dealer: 1, recipient: 2, share, blinding })
+            .unwrap();
+        party.justify().unwrap();
+        assert_eq!(party.commit().err(), Some(DkgError::NoQualifiedDealers));
+    }
+
+    #[test]
+    fn test_dkg_invalid_parameters() {
+        assert_eq!(Dkg::new(1, 0, 3).err(), Some(DkgError::InvalidThreshold));
+        assert_eq!(Dkg::new(1, 4, 3).err(), Some(DkgError::InvalidThreshold));
+        assert_eq!(Dkg::new(0, 2, 3).err(), Some(DkgError::InvalidIndex(0)));
+        assert_eq!(Dkg::new(4, 2, 3).err(), Some(DkgError::InvalidIndex(4)));
+    }
+
+    #[test]
+    fn test_dkg_unexpected_messages() {
+        let mut rng = rand::thread_rng();
+        let mut parties = new_parties(2, 3);
+        assert_eq!(parties[0].complain().err(), Some(DkgError::UnexpectedMessage));
+        assert_eq!(parties[0].commit().err(), Some(DkgError::UnexpectedMessage));
+        assert_eq!(parties[0].finalize().err(), Some(DkgError::UnexpectedMessage));
+
+        let messages = deal(&mut parties);
+        assert_eq!(parties[0].deal(&mut rng).err(), Some(DkgError::UnexpectedMessage));
+
+        // Party 1 receives the share meant for party 3
+        let share = messages.iter().find(|m| m.sender() == 2 && m.recipient() == Some(3)).unwrap();
+        assert_eq!(parties[0].handle_message(share), Err(DkgError::NotRecipient));
+
+        let complaint = DkgMessage::Complaint { complainer: 7, dealer: 1 };
+        assert_eq!(parties[0].handle_message(&complaint), Err(DkgError::InvalidIndex(7)));
+        let complaint = DkgMessage::Complaint { complainer: 2, dealer: 0 };
+        assert_eq!(parties[0].handle_message(&complaint), Err(DkgError::InvalidIndex(0)));
+
+        // Round 1 messages are rejected after complaints are sent
+        parties[0].complain().unwrap();
+        let commitments = messages.iter().find(|m| m.sender() == 2).unwrap();
+        assert_eq!(parties[0].handle_message(commitments), Err(DkgError::UnexpectedMessage));
+
+        // Phase 2 rounds must follow in order
+        assert_eq!(parties[0].reconstruct().err(), Some(DkgError::UnexpectedMessage));
+        parties[0].justify().unwrap();
+        assert_eq!(parties[0].complain_commitments().err(), Some(DkgError::UnexpectedMessage));
+    }
+
+    #[test]
+    fn test_dkg_message_serialization() {
+        let mut rng = rand::thread_rng();
+        let sk = SecretKey::random(&mut rng);
+        let blinding = SecretKey::random(&mut rng);
+        let commitments = vec![PublicKey::from_secret_key(&sk); 3];
+        let messages = vec![
+            DkgMessage::PedersenCommitments { dealer: 1, commitments: commitments.clone() },
+            DkgMessage::Share {
+                dealer: 2,
+                recipient: 3,
+                share: sk.clone(),
+                blinding: blinding.clone(),
+            },
+            DkgMessage::Complaint { complainer: 4, dealer: 5 },
+            DkgMessage::Justification {
+                dealer: 6,
+                recipient: 7,
+                share: sk.clone(),
+                blinding: blinding.clone(),
+            },
+            DkgMessage::FeldmanCommitments { dealer: 8, commitments },
+            DkgMessage::CommitmentComplaint {
+                complainer: 9,
+                dealer: 10,
+                share: sk.clone(),
+                blinding: blinding.clone(),
+            },
+            DkgMessage::Reconstruction { sender: 11, dealer: 12, share: sk, blinding },
+        ];
+
+        for message in messages {
+            let bytes = message.as_bytes();
+            assert_eq!(DkgMessage::from_bytes(&bytes).unwrap(), message);
+            assert_eq!(
+                DkgMessage::from_bytes(&bytes[..bytes.len() - 1]),
+                Err(DkgError::InvalidMessage)
+            );
+        }
+
+        assert_eq!(DkgMessage::from_bytes(&[]), Err(DkgError::InvalidMessage));
+        assert_eq!(DkgMessage::from_bytes(&[7, 0, 0, 0, 1]), Err(DkgError::InvalidMessage));
+        assert_eq!(DkgMessage::from_bytes(&[0, 0, 0, 0, 1]), Err(DkgError::InvalidMessage));
+        assert_eq!(DkgMessage::from_bytes(&[4, 0, 0, 0, 1]), Err(DkgError::InvalidMessage));
+        let zero_share = [&[1u8, 0, 0, 0, 1, 0, 0, 0, 2][..], &[0u8; 64][..]].concat();
+        assert_eq!(
+            DkgMessage::from_bytes(&zero_share),
+            Err(DkgError::InvalidEncoding(Error::SecretKeyOutOfRange))
+        );
+
+        let error: Box<dyn std::error::Error> =
+            Box::new(DkgError::InvalidEncoding(Error::SecretKeyOutOfRange));
+        assert!(error.source().is_some());
+        assert!(error.to_string().starts_with("invalid key in message: "));
+    }
+
+    #[test]
+    fn test_readme() {
+        // This is an exact replica of the README.md at the top level.
+        let (threshold, n) = (2, 3);
+        let mut parties: Vec<Dkg> = (1..=n).map(|i| Dkg::new(i, threshold, n).unwrap()).collect();
+
+        // Each round produces messages for the transport to deliver to the other parties.
+        let mut rng = rand::thread_rng();
+        for round in 0..6 {
+            let messages: Vec<DkgMessage> = parties
+                .iter_mut()
+                .flat_map(|party| {
+                    match round {
+                        0 => party.deal(&mut rng),
+                        1 => party.complain(),
+                        2 => party.justify(),
+                        3 => party.commit(),
+                        4 => party.complain_commitments(),
+                        _ => party.reconstruct(),
+                    }
+                    .unwrap()
+                })
+                .collect();
+
+            for message in messages.iter() {
+                let message = DkgMessage::from_bytes(&message.as_bytes()).unwrap();
+                for party in parties.iter_mut() {
+                    let addressed = message.recipient().map_or(true, |r| r == party.index());
+                    if party.index() != message.sender() && addressed {
+                        party.handle_message(&message).unwrap();
+                    }
+                }
+            }
+        }
+
+        // Reconstruct dealers who withheld valid Feldman commitments and output each share.
+        let outputs: Vec<DkgOutput> =
+            parties.iter_mut().map(|party| party.finalize().unwrap()).collect();
+
+        // Any 2 parties can sign for the group PublicKey which no party knows the SecretKey of.
+        let message = "cats".as_bytes();
+        let partial_signatures: Vec<(u32, Signature)> = outputs[1..]
+            .iter()
+            .map(|output| (output.index, Signature::new(message, &output.secret_share)))
+            .collect();
+        let signature = Signature::combine_threshold(&partial_signatures).unwrap();
+        assert!(signature.verify(message, &outputs[0].public_key));
+    }
+}
@@ -0,0 +1,193 @@
+extern crate amcl;
+
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fmt;
+
+use super::amcl_utils::{AmclError, G1_BYTES, G2_BYTES, SECRET_KEY_BYTES};
+
+/// Errors when decoding, aggregating or hashing BLS types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The input is not the expected number of bytes.
+    InvalidLength { expected: usize },
+    /// The input is shorter than the minimum number of bytes.
+    TooShort { min: usize },
+    /// The compression, infinity or sign flags of an encoded point are invalid.
+    InvalidFlags,
+    /// The encoded coordinates are not a point on the curve.
+    NotOnCurve,
+    /// The point is not in the prime order subgroup.
+    NotInSubgroup,
+    /// The PublicKey is the point at infinity.
+    InfinityPublicKey,
+    /// There are no points to aggregate.
+    EmptyAggregate,
+    /// The SecretKey is zero or not less than the curve order.
+    SecretKeyOutOfRange,
+    /// The domain separation tag is empty.
+    InvalidDst,
+    /// Hashing to the field failed as the requested output is too long.
+    HashToField,
+}
+
+impl From<AmclError> for Error {
+    fn from(e: AmclError) -> Self {
+        match e {
+            AmclError::AggregateEmptyPoints => Error::EmptyAggregate,
+            AmclError::HashToFieldError => Error::HashToField,
+            AmclError::InvalidSecretKeySize => Error::InvalidLength { expected: SECRET_KEY_BYTES },
+            AmclError::InvalidSecretKeyRange => Error::SecretKeyOutOfRange,
+            AmclError::InvalidPoint => Error::NotOnCurve,
+            AmclError::InvalidG1Size => Error::InvalidLength { expected: G1_BYTES },
+            AmclError::InvalidG2Size => Error::InvalidLength { expected: G2_BYTES },
+            AmclError::InvalidYFlag => Error::InvalidFlags,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidLength { expected } => write!(f, "expected {} bytes", expected),
+            Error::TooShort { min } => write!(f, "expected at least {} bytes", min),
+            Error::InvalidFlags => write!(f, "invalid point encoding flags"),
+            Error::NotOnCurve => write!(f, "point is not on the curve"),
+            Error::NotInSubgroup => write!(f, "point is not in the prime order subgroup"),
+            Error::InfinityPublicKey => write!(f, "public key is the point at infinity"),
+            Error::EmptyAggregate => write!(f, "no points to aggregate"),
+            Error::SecretKeyOutOfRange => write!(f, "secret key is not in the range [1, r)"),
+            Error::InvalidDst => write!(f, "domain separation tag is empty"),
+            Error::HashToField => write!(f, "hash to field output is too long"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Reasons a Signature or AggregateSignature failed verification.
+///
+/// Only `PairingMismatch` means the inputs were well formed but the Signature is not valid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// A Signature is not in the prime order subgroup.
+    SignatureNotInSubgroup,
+    /// The AggregatePublicKey is the point at infinity.
+    InfinityPublicKey,
+    /// The number of messages differs from the number of PublicKeys.
+    LengthMismatch { messages: usize, public_keys: usize },
+    /// There are no PublicKeys.
+    EmptyInput,
+    /// The ciphersuite requires distinct messages.
+    DuplicateMessages,
+    /// The domain separation tag is empty.
+    InvalidDst,
+    /// The bits of randomness for batch verification are less than `MIN_RAND_BITS` or greater
+    /// than `MAX_RAND_BITS`.
+    InvalidRandBits,
+    /// The pairing check failed.
+    PairingMismatch,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyError::SignatureNotInSubgroup => {
+                write!(f, "signature is not in the prime order subgroup")
+            }
+            VerifyError::InfinityPublicKey => write!(f, "public key is the point at infinity"),
+            VerifyError::LengthMismatch { messages, public_keys } => {
+                write!(f, "{} messages for {} public keys", messages, public_keys)
+            }
+            VerifyError::EmptyInput => write!(f, "no public keys"),
+            VerifyError::DuplicateMessages => write!(f, "messages are not distinct"),
+            VerifyError::InvalidDst => write!(f, "domain separation tag is empty"),
+            VerifyError::InvalidRandBits => write!(f, "invalid bits of randomness"),
+            VerifyError::PairingMismatch => write!(f, "signature does not match"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifyError {}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::super::keys::{Keypair, PublicKey, SecretKey};
+    use super::super::signature::Signature;
+    use super::*;
+
+    #[test]
+    fn test_from_amcl_error() {
+        assert_eq!(Error::from(AmclError::AggregateEmptyPoints), Error::EmptyAggregate);
+        assert_eq!(Error::from(AmclError::HashToFieldError), Error::HashToField);
+        assert_eq!(
+            Error::from(AmclError::InvalidSecretKeySize),
+            Error::InvalidLength { expected: SECRET_KEY_BYTES }
+        );
+        assert_eq!(Error::from(AmclError::InvalidSecretKeyRange), Error::SecretKeyOutOfRange);
+        assert_eq!(Error::from(AmclError::InvalidPoint), Error::NotOnCurve);
+        assert_eq!(
+            Error::from(AmclError::InvalidG1Size),
+            Error::InvalidLength { expected: G1_BYTES }
+        );
+        assert_eq!(
+            Error::from(AmclError::InvalidG2Size),
+            Error::InvalidLength { expected: G2_BYTES }
+        );
+        assert_eq!(Error::from(AmclError::InvalidYFlag), Error::InvalidFlags);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Error::InvalidLength { expected: G1_BYTES }.to_string(), "expected 48 bytes");
+        assert_eq!(Error::TooShort { min: 32 }.to_string(), "expected at least 32 bytes");
+        assert_eq!(Error::NotInSubgroup.to_string(), "point is not in the prime order subgroup");
+
+        let error: Box<dyn std::error::Error> = Box::new(Error::EmptyAggregate);
+        assert_eq!(error.to_string(), "no points to aggregate");
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let pk_bytes = keypair.pk.as_bytes();
+
+        // Compressed points must set the compression flag and uncompressed points must not
+        let mut bytes = pk_bytes;
+        bytes[0] &= 0b_0111_1111;
+        assert_eq!(PublicKey::from_bytes(&bytes), Err(Error::InvalidFlags));
+        let mut bytes = keypair.pk.as_uncompressed_bytes();
+        bytes[0] |= 0b_1000_0000;
+        assert_eq!(PublicKey::from_uncompressed_bytes(&bytes), Err(Error::InvalidFlags));
+        let mut bytes = keypair.pk.as_uncompressed_bytes();
+        bytes[0] |= 0b_0010_0000;
+        assert_eq!(PublicKey::from_uncompressed_bytes(&bytes), Err(Error::InvalidFlags));
+
+        // Infinity must have no other bits set
+        let mut bytes = [0u8; G2_BYTES];
+        bytes[0] = 0b_1100_0000;
+        bytes[G2_BYTES - 1] = 1;
+        assert_eq!(Signature::from_bytes(&bytes), Err(Error::InvalidFlags));
+
+        // x >= p is not a coordinate of a point on the curve
+        let mut bytes = [0xff; G1_BYTES];
+        bytes[0] = 0b_1001_1111;
+        assert_eq!(PublicKey::from_bytes(&bytes), Err(Error::NotOnCurve));
+
+        // Lengths are distinguished by their expected number of bytes
+        assert_eq!(
+            PublicKey::from_bytes(&keypair.pk.as_uncompressed_bytes()),
+            Err(Error::InvalidLength { expected: G1_BYTES })
+        );
+        assert_eq!(
+            PublicKey::from_uncompressed_bytes(&pk_bytes),
+            Err(Error::InvalidLength { expected: G1_BYTES * 2 })
+        );
+        assert_eq!(SecretKey::from_bytes(&[0; SECRET_KEY_BYTES]), Err(Error::SecretKeyOutOfRange));
+    }
+}
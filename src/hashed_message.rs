@@ -0,0 +1,106 @@
+extern crate amcl;
+
+use super::amcl_utils::{hash_to_curve_g2, hash_to_curve_g2_with_dst, validate_dst, GroupG2};
+use super::ciphersuite::{hash_message, Ciphersuite};
+use super::error::Error;
+use super::keys::VerifyingKey;
+
+/// A message hashed to a point in G2.
+///
+/// Hashing to curve is a significant part of signing and verification, a HashedMessage allows the
+/// hash of a message which is signed or verified many times to be computed once and reused.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct HashedMessage(pub GroupG2);
+
+impl HashedMessage {
+    /// Hash a message to G2.
+    ///
+    /// Uses the Proof of Possession ciphersuite.
+    pub fn new(msg: &[u8]) -> Self {
+        HashedMessage(hash_to_curve_g2(msg))
+    }
+
+    /// Hash a message to G2 using the given Ciphersuite.
+    ///
+    /// The PublicKey is only used by the MessageAugmentationScheme, which prepends it to the message.
+    pub fn new_with_ciphersuite<C: Ciphersuite>(msg: &[u8], pk: &impl VerifyingKey) -> Self {
+        HashedMessage(hash_message::<C>(msg, pk))
+    }
+
+    /// Hash a message to G2 using a custom domain separation tag. Errors if the DST is empty.
+    pub fn new_with_dst(msg: &[u8], dst: &[u8]) -> Result<Self, Error> {
+        let dst = validate_dst(dst)?;
+        Ok(HashedMessage(hash_to_curve_g2_with_dst(msg, dst)))
+    }
+
+    /// The point in G2.
+    pub fn point(&self) -> &GroupG2 {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+
+    use super::super::ciphersuite::{
+        BasicScheme, MessageAugmentationScheme, ProofOfPossessionScheme,
+    };
+    use super::super::keys::Keypair;
+    use super::super::signature::Signature;
+    use super::*;
+
+    #[test]
+    fn test_hashed_message_matches_signing() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = b"hashed";
+
+        let hashed = HashedMessage::new(msg);
+        assert_eq!(
+            hashed,
+            HashedMessage::new_with_ciphersuite::<ProofOfPossessionScheme>(msg, &keypair.pk)
+        );
+        assert_eq!(
+            Signature::new_prehashed(&hashed, &keypair.sk),
+            Signature::new(msg, &keypair.sk)
+        );
+
+        let hashed = HashedMessage::new_with_ciphersuite::<BasicScheme>(msg, &keypair.pk);
+        assert_eq!(
+            Signature::new_prehashed(&hashed, &keypair.sk),
+            Signature::new_with_ciphersuite::<BasicScheme>(msg, &keypair.sk)
+        );
+
+        let hashed =
+            HashedMessage::new_with_ciphersuite::<MessageAugmentationScheme>(msg, &keypair.pk);
+        assert_eq!(
+            Signature::new_prehashed(&hashed, &keypair.sk),
+            Signature::new_with_ciphersuite::<MessageAugmentationScheme>(msg, &keypair.sk)
+        );
+
+        let hashed = HashedMessage::new_with_dst(msg, b"DST").unwrap();
+        assert_eq!(
+            Signature::new_prehashed(&hashed, &keypair.sk),
+            Signature::new_with_dst(msg, &keypair.sk, b"DST").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hashed_message_distinct() {
+        let pk = Keypair::random(&mut rand::thread_rng()).pk;
+        let other_pk = Keypair::random(&mut rand::thread_rng()).pk;
+        let msg = b"hashed";
+
+        assert_ne!(HashedMessage::new(msg), HashedMessage::new(b"other"));
+        assert_ne!(
+            HashedMessage::new(msg),
+            HashedMessage::new_with_ciphersuite::<BasicScheme>(msg, &pk)
+        );
+        assert_ne!(
+            HashedMessage::new_with_ciphersuite::<MessageAugmentationScheme>(msg, &pk),
+            HashedMessage::new_with_ciphersuite::<MessageAugmentationScheme>(msg, &other_pk)
+        );
+        assert_eq!(HashedMessage::new_with_dst(msg, b""), Err(Error::InvalidDst));
+    }
+}
@@ -0,0 +1,185 @@
+extern crate amcl;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::{fmt, str::FromStr};
+#[cfg(feature = "std")]
+use std::{fmt, str::FromStr};
+
+use super::aggregates::{AggregatePublicKey, AggregateSignature};
+use super::error::Error;
+use super::keys::PublicKey;
+use super::signature::Signature;
+
+/// Errors when parsing a 0x-prefixed hex string.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParseHexError {
+    /// The string does not begin with `0x`.
+    MissingPrefix,
+    /// The string is not an even number of hex digits.
+    InvalidHex,
+    /// The bytes are not a valid point.
+    InvalidBytes(Error),
+}
+
+impl fmt::Display for ParseHexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseHexError::MissingPrefix => write!(f, "hex string must be 0x-prefixed"),
+            ParseHexError::InvalidHex => write!(f, "expected an even number of hex digits"),
+            ParseHexError::InvalidBytes(e) => write!(f, "invalid bytes: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseHexError {}
+
+// Write bytes as lowercase hex.
+fn write_hex(f: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes {
+        write!(f, "{:02x}", byte)?;
+    }
+    Ok(())
+}
+
+// Decode a 0x-prefixed hex string, either case is accepted.
+fn decode_hex(s: &str) -> Result<Vec<u8>, ParseHexError> {
+    let digits = match s.strip_prefix("0x") {
+        Some(digits) => digits.as_bytes(),
+        None => return Err(ParseHexError::MissingPrefix),
+    };
+    if digits.len() % 2 != 0 {
+        return Err(ParseHexError::InvalidHex);
+    }
+
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16).ok_or(ParseHexError::InvalidHex)?;
+            let low = (pair[1] as char).to_digit(16).ok_or(ParseHexError::InvalidHex)?;
+            Ok((high << 4 | low) as u8)
+        })
+        .collect()
+}
+
+// Implements LowerHex and Display with `as_bytes` and FromStr with the validating `from_bytes`.
+//
+// As for integers the `0x` prefix is only written by LowerHex with the alternate flag, `{:#x}`.
+macro_rules! impl_hex {
+    ($type:ident) => {
+        impl fmt::LowerHex for $type {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                if f.alternate() {
+                    write!(f, "0x")?;
+                }
+                write_hex(f, &self.as_bytes())
+            }
+        }
+
+        impl fmt::Display for $type {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{:#x}", self)
+            }
+        }
+
+        impl FromStr for $type {
+            type Err = ParseHexError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $type::from_bytes(&decode_hex(s)?).map_err(ParseHexError::InvalidBytes)
+            }
+        }
+    };
+}
+
+impl_hex!(PublicKey);
+impl_hex!(AggregatePublicKey);
+impl_hex!(Signature);
+impl_hex!(AggregateSignature);
+
+#[cfg(test)]
+mod tests {
+    extern crate hex;
+    extern crate rand;
+
+    use super::super::amcl_utils::{G1_BYTES, G2_BYTES};
+    use super::super::keys::Keypair;
+    use super::*;
+
+    #[test]
+    fn test_hex_round_trip() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", &keypair.sk);
+        let hex_public_key = hex::encode(keypair.pk.as_bytes());
+        let hex_signature = hex::encode(signature.as_bytes());
+
+        assert_eq!(keypair.pk.to_string(), format!("0x{}", hex_public_key));
+        assert_eq!(format!("{:x}", keypair.pk), hex_public_key);
+        assert_eq!(format!("{:#x}", keypair.pk), keypair.pk.to_string());
+        assert_eq!(keypair.pk.to_string().parse::<PublicKey>(), Ok(keypair.pk.clone()));
+
+        assert_eq!(signature.to_string(), format!("0x{}", hex_signature));
+        assert_eq!(format!("{:x}", signature), hex_signature);
+        assert_eq!(signature.to_string().parse::<Signature>(), Ok(signature.clone()));
+
+        let aggregate_public_key = AggregatePublicKey::from_public_key(&keypair.pk);
+        assert_eq!(aggregate_public_key.to_string(), keypair.pk.to_string());
+        assert_eq!(
+            aggregate_public_key.to_string().parse::<AggregatePublicKey>(),
+            Ok(aggregate_public_key)
+        );
+
+        let aggregate_signature = AggregateSignature::aggregate(&[&signature]);
+        assert_eq!(aggregate_signature.to_string(), signature.to_string());
+        assert_eq!(
+            aggregate_signature.to_string().parse::<AggregateSignature>(),
+            Ok(aggregate_signature)
+        );
+
+        // Uppercase digits are accepted
+        let upper = format!("0x{}", hex_public_key.to_uppercase());
+        assert_eq!(upper.parse::<PublicKey>(), Ok(keypair.pk));
+    }
+
+    #[test]
+    fn test_hex_from_str_invalid() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let hex_public_key = hex::encode(keypair.pk.as_bytes());
+
+        assert_eq!(hex_public_key.parse::<PublicKey>(), Err(ParseHexError::MissingPrefix));
+        assert_eq!("0X00".parse::<PublicKey>(), Err(ParseHexError::MissingPrefix));
+        assert_eq!(
+            format!("0x{}0", hex_public_key).parse::<PublicKey>(),
+            Err(ParseHexError::InvalidHex)
+        );
+        assert_eq!("0xzz".parse::<Signature>(), Err(ParseHexError::InvalidHex));
+        assert_eq!("0x+f".parse::<Signature>(), Err(ParseHexError::InvalidHex));
+        assert_eq!(
+            format!("0x{}", hex_public_key).parse::<Signature>(),
+            Err(ParseHexError::InvalidBytes(Error::InvalidLength { expected: G2_BYTES }))
+        );
+
+        // Points are validated
+        let mut infinity = [0u8; G1_BYTES];
+        infinity[0] = 0xc0;
+        let infinity = format!("0x{}", hex::encode(infinity));
+        assert_eq!(
+            infinity.parse::<PublicKey>(),
+            Err(ParseHexError::InvalidBytes(Error::InfinityPublicKey))
+        );
+        assert!(infinity.parse::<AggregatePublicKey>().is_ok());
+        let invalid = format!("0x{}", hex::encode([0xff; G2_BYTES]));
+        assert!(invalid.parse::<Signature>().is_err());
+    }
+
+    #[test]
+    fn test_parse_hex_error_display() {
+        assert_eq!(ParseHexError::MissingPrefix.to_string(), "hex string must be 0x-prefixed");
+        assert_eq!(
+            ParseHexError::InvalidBytes(Error::InfinityPublicKey).to_string(),
+            "invalid bytes: public key is the point at infinity"
+        );
+    }
+}
@@ -3,12 +3,22 @@ extern crate rand;
 extern crate zeroize;
 
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, vec::Vec};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
 
 use self::zeroize::Zeroize;
+use super::aggregates::AggregatePublicKey;
 use super::amcl_utils::{
-    self, compress_g1, decompress_g1, g1mul, subgroup_check_g1, AmclError, Big, GroupG1,
-    CURVE_ORDER, G1_BYTES, SECRET_KEY_BYTES,
+    compress_g1, compress_g2, decompress_g1, decompress_g2, deserialize_uncompressed_g1,
+    g1_generator_mul, g1mul, g2_generator_mul, subgroup_check_g1, subgroup_check_g2, Big, GroupG1,
+    GroupG2, CURVE_ORDER, G1_BYTES, G2_BYTES, SECRET_KEY_BYTES,
+};
+use super::derivation::{derive_path, DerivationPath};
+use super::error::Error;
+use super::proof_of_possession::{MinSigProofOfPossession, ProofOfPossession};
+use super::threshold::{
+    lagrange_coefficients, split, split_verifiable, ThresholdError, VerifiableShares,
 };
 
 use amcl::hash256::HASH256;
@@ -16,7 +26,7 @@ use rand::Rng;
 #[cfg(feature = "std")]
 use std::fmt;
 use BLSCurve::bls381::utils::{
-    deserialize_g1, secret_key_from_bytes, secret_key_to_bytes, serialize_uncompressed_g1,
+    secret_key_from_bytes, secret_key_to_bytes, serialize_uncompressed_g1,
 };
 
 // Key Generation Constants
@@ -42,9 +52,9 @@ impl SecretKey {
     ///
     /// Generate a new SecretKey based off Initial Keying Material (IKM) and key info.
     /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-02#section-2.3
-    pub fn key_generate(ikm: &[u8], key_info: &[u8]) -> Result<Self, AmclError> {
+    pub fn key_generate(ikm: &[u8], key_info: &[u8]) -> Result<Self, Error> {
         if ikm.len() < 32 {
-            return Err(AmclError::InvalidSecretKeySize);
+            return Err(Error::TooShort { min: 32 });
         }
 
         let mut sk = Big::new();
@@ -77,7 +87,7 @@ impl SecretKey {
     }
 
     /// Instantiate a SecretKey from existing bytes.
-    pub fn from_bytes(input: &[u8]) -> Result<SecretKey, AmclError> {
+    pub fn from_bytes(input: &[u8]) -> Result<SecretKey, Error> {
         Ok(Self { x: secret_key_from_bytes(input)? })
     }
 
@@ -89,12 +99,71 @@ impl SecretKey {
     pub fn as_raw(&self) -> &Big {
         &self.x
     }
+
+    pub(crate) fn from_raw(x: Big) -> Self {
+        Self { x }
+    }
+
+    /// Derive the SecretKey at an EIP-2334 DerivationPath from a seed of at least 32 bytes.
+    ///
+    /// https://eips.ethereum.org/EIPS/eip-2333
+    pub fn derive_path(seed: &[u8], path: &DerivationPath) -> Result<Self, Error> {
+        derive_path(seed, path)
+    }
+
+    /// The PublicKey of this SecretKey.
+    ///
+    /// With `std` the G1 generator is multiplied using a precomputed fixed-base table.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from_secret_key(self)
+    }
+
+    /// PopProve
+    ///
+    /// Generate a ProofOfPossession for the PublicKey of this SecretKey.
+    pub fn pop_prove(&self) -> ProofOfPossession {
+        ProofOfPossession::new(self)
+    }
+
+    /// PopProve
+    ///
+    /// Generate a MinSigProofOfPossession for the MinSigPublicKey of this SecretKey.
+    pub fn min_sig_pop_prove(&self) -> MinSigProofOfPossession {
+        MinSigProofOfPossession::new(self)
+    }
+
+    /// Split the SecretKey into `n` shares with indices 1, ..., n using Shamir secret sharing.
+    ///
+    /// Any `threshold` shares can combine their PublicKeys or Signatures with `combine_threshold`.
+    /// https://dl.acm.org/doi/10.1145/359168.359176
+    pub fn split<R: Rng + ?Sized>(
+        &self,
+        threshold: usize,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<Vec<(u32, SecretKey)>, ThresholdError> {
+        split(self, threshold, n, rng)
+    }
+
+    /// Split the SecretKey into `n` shares along with Feldman commitments to the polynomial.
+    ///
+    /// Each share holder can check their share with `verify_share` and derive every participant's
+    /// PublicKey share with `public_key_share`. The first commitment is the PublicKey.
+    /// https://www.cs.umd.edu/~gasarch/TOPICS/secretsharing/feldmanVSS.pdf
+    pub fn split_verifiable<R: Rng + ?Sized>(
+        &self,
+        threshold: usize,
+        n: usize,
+        rng: &mut R,
+    ) -> Result<VerifiableShares, ThresholdError> {
+        split_verifiable(self, threshold, n, rng)
+    }
 }
 
 #[cfg(feature = "std")]
 impl fmt::Debug for SecretKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.x.to_string())
+        write!(f, "SecretKey(<redacted>)")
     }
 }
 
@@ -122,32 +191,24 @@ pub struct PublicKey {
 impl PublicKey {
     /// Instantiate a PublicKey from some SecretKey.
     pub fn from_secret_key(sk: &SecretKey) -> Self {
-        PublicKey {
-            point: {
-                #[cfg(feature = "std")]
-                {
-                    g1mul(&amcl_utils::GENERATORG1, sk.as_raw())
-                }
-                #[cfg(not(feature = "std"))]
-                {
-                    g1mul(&amcl_utils::GroupG1::generator(), sk.as_raw())
-                }
-            },
-        }
+        PublicKey { point: g1_generator_mul(sk.as_raw()) }
     }
 
     /// Instantiate a PublicKey from compressed bytes.
-    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, AmclError> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<PublicKey, Error> {
         let public_key = Self::from_bytes_unchecked(bytes)?;
-        if !public_key.key_validate() {
-            return Err(AmclError::InvalidPoint);
+        if public_key.point.is_infinity() {
+            return Err(Error::InfinityPublicKey);
+        }
+        if !subgroup_check_g1(&public_key.point) {
+            return Err(Error::NotInSubgroup);
         }
 
         Ok(public_key)
     }
 
     /// Instantiate a PublicKey from compressed bytes.
-    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<PublicKey, AmclError> {
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<PublicKey, Error> {
         let point = decompress_g1(bytes)?;
         let public_key = Self { point };
 
@@ -167,11 +228,8 @@ impl PublicKey {
     /// InstantiatePublicKey from uncompress (x, y) bytes
     ///
     /// Does not validate the key, MUST only be used on verified keys.
-    pub fn from_uncompressed_bytes(bytes: &[u8]) -> Result<PublicKey, AmclError> {
-        if bytes.len() != G1_BYTES * 2 {
-            return Err(AmclError::InvalidG1Size);
-        }
-        Ok(Self { point: deserialize_g1(bytes)? })
+    pub fn from_uncompressed_bytes(bytes: &[u8]) -> Result<PublicKey, Error> {
+        Ok(Self { point: deserialize_uncompressed_g1(bytes)? })
     }
 
     /// KeyValidate
@@ -184,6 +242,174 @@ impl PublicKey {
         }
         true
     }
+
+    /// PopVerify
+    ///
+    /// Verifies a ProofOfPossession for this PublicKey.
+    pub fn pop_verify(&self, proof: &ProofOfPossession) -> bool {
+        proof.verify(self)
+    }
+
+    /// Combine at least `threshold` PublicKey shares into the PublicKey of the split SecretKey.
+    ///
+    /// PK = sum(lambda_i * PK_i) where lambda_i are the Lagrange coefficients at 0.
+    pub fn combine_threshold(shares: &[(u32, PublicKey)]) -> Result<Self, ThresholdError> {
+        let indices: Vec<u32> = shares.iter().map(|(index, _)| *index).collect();
+        let coefficients = lagrange_coefficients(&indices)?;
+
+        let mut point = GroupG1::new();
+        for ((_, share), coefficient) in shares.iter().zip(coefficients.iter()) {
+            point.add(&g1mul(&share.point, coefficient));
+        }
+        Ok(Self { point })
+    }
+}
+
+/// A PublicKey in G1 which Signatures and AggregateSignatures can be verified against.
+///
+/// Implemented by PublicKey, AggregatePublicKey and PreparedPublicKey. The trait is sealed so
+/// that only points validated by this crate are verified against.
+pub trait VerifyingKey: sealed::Sealed {
+    /// The PublicKey point.
+    fn point(&self) -> &GroupG1;
+
+    /// The PublicKey point in affine coordinates, as required for pairings.
+    fn affine_point(&self) -> Cow<'_, GroupG1> {
+        let mut point = self.point().clone();
+        point.affine();
+        Cow::Owned(point)
+    }
+
+    /// Export the PublicKey to compressed bytes.
+    fn compressed_bytes(&self) -> [u8; G1_BYTES] {
+        compress_g1(self.point())
+    }
+}
+
+pub(crate) mod sealed {
+    /// Restricts VerifyingKey to the key types of this crate.
+    pub trait Sealed {}
+}
+
+impl sealed::Sealed for PublicKey {}
+
+impl VerifyingKey for PublicKey {
+    fn point(&self) -> &GroupG1 {
+        &self.point
+    }
+}
+
+/// A PublicKey or AggregatePublicKey prepared for repeated verification.
+///
+/// Stores only the affine point and compressed bytes so they are not recomputed by each
+/// verification. No Miller loop line coefficients are cached, amcl computes them from the G2
+/// point of each pairing.
+///
+/// Accepted by the `_prepared` variants of verification and by the `_with_dst`, `_prehashed`
+/// and `_with_rand_bits` variants, except those which first aggregate a list of PublicKeys.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct PreparedPublicKey {
+    point: GroupG1,
+    bytes: [u8; G1_BYTES],
+}
+
+impl PreparedPublicKey {
+    /// Prepare a PublicKey or AggregatePublicKey for verification.
+    pub fn new<K: VerifyingKey + ?Sized>(key: &K) -> Self {
+        Self { point: key.affine_point().into_owned(), bytes: key.compressed_bytes() }
+    }
+
+    /// Export the PublicKey to compressed bytes.
+    pub fn as_bytes(&self) -> [u8; G1_BYTES] {
+        self.bytes
+    }
+}
+
+impl sealed::Sealed for PreparedPublicKey {}
+
+impl VerifyingKey for PreparedPublicKey {
+    fn point(&self) -> &GroupG1 {
+        &self.point
+    }
+
+    fn affine_point(&self) -> Cow<'_, GroupG1> {
+        Cow::Borrowed(&self.point)
+    }
+
+    fn compressed_bytes(&self) -> [u8; G1_BYTES] {
+        self.bytes
+    }
+}
+
+impl<'a> From<&'a PublicKey> for PreparedPublicKey {
+    fn from(public_key: &'a PublicKey) -> Self {
+        Self::new(public_key)
+    }
+}
+
+impl<'a> From<&'a AggregatePublicKey> for PreparedPublicKey {
+    fn from(aggregate_public_key: &'a AggregatePublicKey) -> Self {
+        Self::new(aggregate_public_key)
+    }
+}
+
+/// A BLS public key for the minimal-signature-size variant.
+///
+/// The PublicKey is a point in G2 and is used to verify MinSigSignatures which are points in G1.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MinSigPublicKey {
+    pub point: GroupG2,
+}
+
+impl MinSigPublicKey {
+    /// Instantiate a MinSigPublicKey from some SecretKey.
+    pub fn from_secret_key(sk: &SecretKey) -> Self {
+        MinSigPublicKey { point: g2_generator_mul(sk.as_raw()) }
+    }
+
+    /// Instantiate a MinSigPublicKey from compressed bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<MinSigPublicKey, Error> {
+        let public_key = Self::from_bytes_unchecked(bytes)?;
+        if public_key.point.is_infinity() {
+            return Err(Error::InfinityPublicKey);
+        }
+        if !subgroup_check_g2(&public_key.point) {
+            return Err(Error::NotInSubgroup);
+        }
+
+        Ok(public_key)
+    }
+
+    /// Instantiate a MinSigPublicKey from compressed bytes.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<MinSigPublicKey, Error> {
+        let point = decompress_g2(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// Export the MinSigPublicKey to compressed bytes.
+    pub fn as_bytes(&self) -> [u8; G2_BYTES] {
+        compress_g2(&self.point)
+    }
+
+    /// KeyValidate
+    ///
+    /// Verifies a public key is valid
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-2.5
+    pub fn key_validate(&self) -> bool {
+        if self.point.is_infinity() || !subgroup_check_g2(&self.point) {
+            return false;
+        }
+        true
+    }
+
+    /// PopVerify
+    ///
+    /// Verifies a MinSigProofOfPossession for this MinSigPublicKey.
+    pub fn pop_verify(&self, proof: &MinSigProofOfPossession) -> bool {
+        proof.verify(self)
+    }
 }
 
 /// A helper which stores a BLS public and private key pair.
@@ -208,6 +434,8 @@ mod tests {
     extern crate hex;
     extern crate rand;
 
+    use super::super::amcl_utils::g2mul;
+    use super::super::error::VerifyError;
     use super::super::signature::Signature;
     use super::*;
 
@@ -260,16 +488,28 @@ mod tests {
     #[test]
     fn test_public_key_uncompressed_serialization_incorrect_size() {
         let bytes = vec![1; 1];
-        assert_eq!(PublicKey::from_uncompressed_bytes(&bytes), Err(AmclError::InvalidG1Size));
+        assert_eq!(
+            PublicKey::from_uncompressed_bytes(&bytes),
+            Err(Error::InvalidLength { expected: G1_BYTES * 2 })
+        );
 
         let bytes = vec![1; 95];
-        assert_eq!(PublicKey::from_uncompressed_bytes(&bytes), Err(AmclError::InvalidG1Size));
+        assert_eq!(
+            PublicKey::from_uncompressed_bytes(&bytes),
+            Err(Error::InvalidLength { expected: G1_BYTES * 2 })
+        );
 
         let bytes = vec![1; 97];
-        assert_eq!(PublicKey::from_uncompressed_bytes(&bytes), Err(AmclError::InvalidG1Size));
+        assert_eq!(
+            PublicKey::from_uncompressed_bytes(&bytes),
+            Err(Error::InvalidLength { expected: G1_BYTES * 2 })
+        );
 
         let bytes = vec![];
-        assert_eq!(PublicKey::from_uncompressed_bytes(&bytes), Err(AmclError::InvalidG1Size));
+        assert_eq!(
+            PublicKey::from_uncompressed_bytes(&bytes),
+            Err(Error::InvalidLength { expected: G1_BYTES * 2 })
+        );
     }
 
     #[test]
@@ -278,22 +518,34 @@ mod tests {
         let mut bytes = vec![0; 96];
         bytes[47] = 1;
         bytes[95] = 1;
-        assert_eq!(PublicKey::from_uncompressed_bytes(&bytes), Err(AmclError::InvalidPoint));
+        assert_eq!(PublicKey::from_uncompressed_bytes(&bytes), Err(Error::NotOnCurve));
+    }
+
+    #[test]
+    fn test_secret_key_key_generate_short_ikm() {
+        assert_eq!(SecretKey::key_generate(&[1u8; 31], &[]), Err(Error::TooShort { min: 32 }));
+        assert!(SecretKey::key_generate(&[1u8; 32], &[]).is_ok());
     }
 
     #[test]
     fn test_secret_key_from_bytes() {
         let bytes = vec![];
-        assert_eq!(SecretKey::from_bytes(&bytes), Err(AmclError::InvalidSecretKeySize));
+        assert_eq!(
+            SecretKey::from_bytes(&bytes),
+            Err(Error::InvalidLength { expected: SECRET_KEY_BYTES })
+        );
 
         let bytes = vec![1; 33];
-        assert_eq!(SecretKey::from_bytes(&bytes), Err(AmclError::InvalidSecretKeySize));
+        assert_eq!(
+            SecretKey::from_bytes(&bytes),
+            Err(Error::InvalidLength { expected: SECRET_KEY_BYTES })
+        );
 
         let bytes = vec![0; 32];
-        assert_eq!(SecretKey::from_bytes(&bytes), Err(AmclError::InvalidSecretKeyRange));
+        assert_eq!(SecretKey::from_bytes(&bytes), Err(Error::SecretKeyOutOfRange));
 
         let bytes = vec![255; 32];
-        assert_eq!(SecretKey::from_bytes(&bytes), Err(AmclError::InvalidSecretKeyRange));
+        assert_eq!(SecretKey::from_bytes(&bytes), Err(Error::SecretKeyOutOfRange));
     }
 
     #[test]
@@ -336,7 +588,7 @@ mod tests {
         let mut pk_bytes = vec![0; 48];
         pk_bytes[0] = 128;
 
-        assert_eq!(PublicKey::from_bytes(&pk_bytes), Err(AmclError::InvalidPoint));
+        assert_eq!(PublicKey::from_bytes(&pk_bytes), Err(Error::NotInSubgroup));
         assert!(PublicKey::from_bytes_unchecked(&pk_bytes).is_ok());
     }
 
@@ -344,9 +596,121 @@ mod tests {
     fn test_public_key_from_bytes_infinity() {
         // Infinity
         let mut pk_bytes = vec![0; 48];
+        pk_bytes[0] = 192;
+
+        assert_eq!(PublicKey::from_bytes(&pk_bytes), Err(Error::InfinityPublicKey));
+
+        // Infinity with the sign flag set
         pk_bytes[0] = 196;
+        assert_eq!(PublicKey::from_bytes(&pk_bytes), Err(Error::InvalidFlags));
+    }
+
+    #[test]
+    fn test_min_sig_public_key_serialization_isomorphism() {
+        for _ in 0..10 {
+            let sk = SecretKey::random(&mut rand::thread_rng());
+            let pk = MinSigPublicKey::from_secret_key(&sk);
+            let decoded_pk = pk.as_bytes().to_vec();
+            let encoded_pk = MinSigPublicKey::from_bytes(&decoded_pk).unwrap();
+            assert_eq!(encoded_pk, pk);
+            assert_eq!(decoded_pk, encoded_pk.as_bytes().to_vec());
+        }
+    }
+
+    #[test]
+    fn test_min_sig_public_key_known_bytes() {
+        // Generated using the min_sig module of blst with the SecretKey of the README.
+        let sk = SecretKey::from_bytes(
+            &hex::decode("4efc7a7e20004b59fc1f2a82fe58065a8aca87c2e975b54b60ee4f64ed3b8c6f")
+                .unwrap(),
+        )
+        .unwrap();
+        let pk = MinSigPublicKey::from_secret_key(&sk);
+        assert_eq!(pk.as_bytes().to_vec(), hex::decode("90872ca7d8607801d49fa2ade80391d4ee0c232eb2dce53503cf55857e7805eb8f9e85d7de7071dc7d4ba8f41fdd623c0dc4906d6a6f3fcc9810eb81a82d53e0c9f4c5ecf6aebecdc07fad65d2093a35ffa613ab40ead9834257eab27136b156").unwrap());
+    }
+
+    #[test]
+    fn test_public_key_fixed_base_matches_g1mul() {
+        for _ in 0..10 {
+            let sk = SecretKey::random(&mut rand::thread_rng());
+            let pk = sk.public_key();
+            assert!(pk.point.equals(&g1mul(&GroupG1::generator(), sk.as_raw())));
+            assert_eq!(pk, PublicKey::from_secret_key(&sk));
+
+            let min_sig_pk = MinSigPublicKey::from_secret_key(&sk);
+            assert!(min_sig_pk.point.equals(&g2mul(&GroupG2::generator(), sk.as_raw())));
+        }
+    }
+
+    #[test]
+    fn test_prepared_public_key_verify() {
+        use super::super::ciphersuite::{hash_message, MessageAugmentationScheme};
+
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let prepared = PreparedPublicKey::from(&keypair.pk);
+        assert_eq!(prepared, PreparedPublicKey::new(&keypair.pk));
+        assert_eq!(prepared.as_bytes(), keypair.pk.as_bytes());
+        assert!(prepared.point().equals(&keypair.pk.point));
+
+        let msg = b"prepared";
+        let other_keypair = Keypair::random(&mut rand::thread_rng());
+        let other_prepared = PreparedPublicKey::from(&other_keypair.pk);
+
+        let signature = Signature::new(msg, &keypair.sk);
+        assert!(signature.verify_prepared(msg, &prepared));
+        assert!(!signature.verify_prepared(b"other", &prepared));
+        assert!(!signature.verify_prepared(msg, &other_prepared));
+
+        // Message augmentation uses the cached compressed bytes
+        assert!(hash_message::<MessageAugmentationScheme>(msg, &prepared).equals(&hash_message::<
+            MessageAugmentationScheme,
+        >(
+            msg,
+            &keypair.pk
+        )));
+    }
+
+    #[test]
+    fn test_prepared_public_key_infinity() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let mut negative_public_key = keypair.pk.clone();
+        negative_public_key.point.neg();
+        let infinity = AggregatePublicKey::aggregate(&[&keypair.pk, &negative_public_key]).unwrap();
+        assert!(infinity.point.is_infinity());
+
+        // The infinity Signature would otherwise verify any message against an infinity key
+        let signature = Signature { point: GroupG2::new() };
+        let prepared = PreparedPublicKey::from(&infinity);
+        assert!(!signature.verify_prepared(b"anything", &prepared));
+        assert_eq!(
+            signature.try_verify_prepared(b"anything", &prepared),
+            Err(VerifyError::InfinityPublicKey)
+        );
+    }
+
+    #[test]
+    fn test_min_sig_public_key_from_bytes_invalid() {
+        // Infinity
+        let mut pk_bytes = vec![0; 96];
+        pk_bytes[0] = 192;
+        assert_eq!(MinSigPublicKey::from_bytes(&pk_bytes), Err(Error::InfinityPublicKey));
+        assert!(MinSigPublicKey::from_bytes_unchecked(&pk_bytes).is_ok());
+
+        // Incorrect size
+        assert_eq!(
+            MinSigPublicKey::from_bytes(&[192; 48]),
+            Err(Error::InvalidLength { expected: G2_BYTES })
+        );
+    }
+
+    #[test]
+    fn test_secret_key_debug_redacted() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let debug = format!("{:?}", keypair);
 
-        assert_eq!(PublicKey::from_bytes(&pk_bytes), Err(AmclError::InvalidPoint));
+        assert_eq!(format!("{:?}", keypair.sk), "SecretKey(<redacted>)");
+        assert!(debug.contains("SecretKey(<redacted>)"));
+        assert!(!debug.contains(&keypair.sk.as_raw().to_string()));
     }
 
     #[test]
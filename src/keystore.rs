@@ -0,0 +1,692 @@
+extern crate aes;
+extern crate amcl;
+extern crate ctr;
+extern crate hex;
+extern crate pbkdf2;
+extern crate rand;
+extern crate scrypt;
+extern crate serde;
+extern crate serde_json;
+extern crate sha2;
+extern crate unicode_normalization;
+extern crate zeroize;
+
+use self::ctr::cipher::{KeyIvInit, StreamCipher};
+use self::serde::{Deserialize, Serialize};
+use self::unicode_normalization::UnicodeNormalization;
+use self::zeroize::Zeroize;
+use super::error::Error;
+use super::keys::{Keypair, PublicKey, SecretKey};
+
+use amcl::hash256::HASH256;
+use rand::Rng;
+use std::fmt;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+// Keystore Constants
+/// EIP-2335 keystore version.
+pub const KEYSTORE_VERSION: u32 = 4;
+/// Length in bytes of the decryption key derived from the password.
+pub const DKLEN: u32 = 32;
+/// Length in bytes of the random KDF salt.
+pub const SALT_BYTES: usize = 32;
+/// Length in bytes of the random AES-128-CTR IV.
+pub const IV_BYTES: usize = 16;
+/// Maximum scrypt log2(n), 4 times the work of the EIP-2335 reference keystore.
+pub const MAX_SCRYPT_LOG_N: u8 = 20;
+/// Maximum scrypt block size `r`, with `MAX_SCRYPT_LOG_N` bounding memory use to 2 GiB.
+pub const MAX_SCRYPT_R: u32 = 16;
+/// Maximum scrypt parallelization `p`.
+pub const MAX_SCRYPT_P: u32 = 16;
+/// Maximum PBKDF2 iteration count `c`, 4 times the EIP-2335 reference keystore.
+pub const MAX_PBKDF2_C: u32 = 1_048_576;
+
+/// Errors when encrypting or decrypting a Keystore.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeystoreError {
+    /// The keystore JSON could not be parsed.
+    InvalidJson(String),
+    /// The keystore is not version 4.
+    InvalidVersion(u32),
+    /// A hex field of the keystore could not be decoded.
+    InvalidHex,
+    /// The KDF parameters are unsupported, invalid or exceed the maximum work factors.
+    InvalidKdfParams,
+    /// The cipher parameters are invalid.
+    InvalidCipherParams,
+    /// The checksum or cipher function is not supported.
+    UnsupportedFunction(String),
+    /// The checksum did not match, most likely due to an incorrect password.
+    InvalidPassword,
+    /// The decrypted secret is not a valid SecretKey.
+    InvalidSecretKey(Error),
+    /// The decrypted SecretKey does not match the pubkey field.
+    PublicKeyMismatch,
+}
+
+impl fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeystoreError::InvalidJson(e) => write!(f, "invalid keystore JSON: {}", e),
+            KeystoreError::InvalidVersion(version) => {
+                write!(f, "unsupported keystore version {}", version)
+            }
+            KeystoreError::InvalidHex => write!(f, "keystore field is not valid hex"),
+            KeystoreError::InvalidKdfParams => write!(f, "invalid or unsupported KDF parameters"),
+            KeystoreError::InvalidCipherParams => write!(f, "invalid cipher parameters"),
+            KeystoreError::UnsupportedFunction(function) => {
+                write!(f, "unsupported function {}", function)
+            }
+            KeystoreError::InvalidPassword => write!(f, "checksum mismatch, invalid password"),
+            KeystoreError::InvalidSecretKey(e) => write!(f, "invalid secret key: {}", e),
+            KeystoreError::PublicKeyMismatch => {
+                write!(f, "secret key does not match the keystore pubkey")
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeystoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KeystoreError::InvalidSecretKey(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// The password based key derivation function used to secure a Keystore.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kdf {
+    /// scrypt, `n` MUST be a power of two.
+    Scrypt { n: u32, r: u32, p: u32 },
+    /// PBKDF2 with HMAC-SHA256.
+    Pbkdf2 { c: u32 },
+}
+
+impl Kdf {
+    /// scrypt with the parameters of the EIP-2335 reference keystore.
+    pub fn scrypt() -> Self {
+        Kdf::Scrypt { n: 262_144, r: 8, p: 1 }
+    }
+
+    /// PBKDF2 with the parameters of the EIP-2335 reference keystore.
+    pub fn pbkdf2() -> Self {
+        Kdf::Pbkdf2 { c: 262_144 }
+    }
+}
+
+/// An EIP-2335 keystore storing a SecretKey encrypted with a password.
+///
+/// https://eips.ethereum.org/EIPS/eip-2335
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    crypto: Crypto,
+    #[serde(default)]
+    description: String,
+    pubkey: String,
+    path: String,
+    uuid: String,
+    version: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Crypto {
+    kdf: KdfModule,
+    checksum: ChecksumModule,
+    cipher: CipherModule,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct KdfModule {
+    #[serde(flatten)]
+    params: KdfParams,
+    message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "function", content = "params", rename_all = "lowercase")]
+enum KdfParams {
+    Scrypt { dklen: u32, n: u32, p: u32, r: u32, salt: String },
+    Pbkdf2 { dklen: u32, c: u32, prf: String, salt: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct ChecksumModule {
+    function: String,
+    params: EmptyParams,
+    message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct EmptyParams {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct CipherModule {
+    function: String,
+    params: CipherParams,
+    message: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+impl Keystore {
+    /// Encrypt a SecretKey with a password, generating a random salt, IV and UUID.
+    ///
+    /// `path` is the EIP-2334 path the SecretKey was derived at, or empty if unknown.
+    pub fn encrypt<R: Rng + ?Sized>(
+        sk: &SecretKey,
+        password: &str,
+        path: &str,
+        kdf: Kdf,
+        rng: &mut R,
+    ) -> Result<Self, KeystoreError> {
+        let mut salt = [0u8; SALT_BYTES];
+        let mut iv = [0u8; IV_BYTES];
+        let mut uuid = [0u8; 16];
+        rng.fill(&mut salt);
+        rng.fill(&mut iv);
+        rng.fill(&mut uuid);
+
+        Self::encrypt_with_params(sk, password, path, kdf, &salt, &iv, &uuid_v4(uuid))
+    }
+
+    // Encrypt a SecretKey using the given salt, IV and UUID.
+    fn encrypt_with_params(
+        sk: &SecretKey,
+        password: &str,
+        path: &str,
+        kdf: Kdf,
+        salt: &[u8],
+        iv: &[u8],
+        uuid: &str,
+    ) -> Result<Self, KeystoreError> {
+        let salt = hex::encode(salt);
+        let params = match kdf {
+            Kdf::Scrypt { n, r, p } => KdfParams::Scrypt { dklen: DKLEN, n, p, r, salt },
+            Kdf::Pbkdf2 { c } => {
+                KdfParams::Pbkdf2 { dklen: DKLEN, c, prf: "hmac-sha256".to_string(), salt }
+            }
+        };
+
+        let mut decryption_key = derive_decryption_key(password, &params)?;
+
+        // cipher_message = AES-128-CTR(DK[0..16], IV, secret)
+        let mut cipher_message = sk.as_bytes();
+        aes_128_ctr(&decryption_key[..16], iv, &mut cipher_message)?;
+        let checksum = checksum(&decryption_key, &cipher_message);
+        decryption_key.zeroize();
+
+        Ok(Self {
+            crypto: Crypto {
+                kdf: KdfModule { params, message: String::new() },
+                checksum: ChecksumModule {
+                    function: "sha256".to_string(),
+                    params: EmptyParams {},
+                    message: hex::encode(checksum),
+                },
+                cipher: CipherModule {
+                    function: "aes-128-ctr".to_string(),
+                    params: CipherParams { iv: hex::encode(iv) },
+                    message: hex::encode(cipher_message),
+                },
+            },
+            description: String::new(),
+            pubkey: format!("{:x}", PublicKey::from_secret_key(sk)),
+            path: path.to_string(),
+            uuid: uuid.to_string(),
+            version: KEYSTORE_VERSION,
+        })
+    }
+
+    /// Decrypt the Keystore with a password.
+    ///
+    /// Verifies the checksum and that the SecretKey matches the pubkey field.
+    pub fn decrypt(&self, password: &str) -> Result<Keypair, KeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::InvalidVersion(self.version));
+        }
+        if self.crypto.checksum.function != "sha256" {
+            return Err(KeystoreError::UnsupportedFunction(self.crypto.checksum.function.clone()));
+        }
+        if self.crypto.cipher.function != "aes-128-ctr" {
+            return Err(KeystoreError::UnsupportedFunction(self.crypto.cipher.function.clone()));
+        }
+
+        let expected_checksum = decode_hex(&self.crypto.checksum.message)?;
+        let iv = decode_hex(&self.crypto.cipher.params.iv)?;
+        let mut secret = decode_hex(&self.crypto.cipher.message)?;
+
+        let mut decryption_key = derive_decryption_key(password, &self.crypto.kdf.params)?;
+
+        // checksum = SHA256(DK[16..32] || cipher_message)
+        if !constant_time_eq(&checksum(&decryption_key, &secret), &expected_checksum) {
+            decryption_key.zeroize();
+            return Err(KeystoreError::InvalidPassword);
+        }
+
+        // secret = AES-128-CTR(DK[0..16], IV, cipher_message)
+        let result = aes_128_ctr(&decryption_key[..16], &iv, &mut secret);
+        decryption_key.zeroize();
+        result?;
+
+        let sk = SecretKey::from_bytes(&secret).map_err(KeystoreError::InvalidSecretKey);
+        secret.zeroize();
+        let sk = sk?;
+
+        let pk = PublicKey::from_secret_key(&sk);
+        if format!("{:x}", pk) != self.pubkey.to_lowercase() {
+            return Err(KeystoreError::PublicKeyMismatch);
+        }
+
+        Ok(Keypair { sk, pk })
+    }
+
+    /// Parse a Keystore from JSON.
+    pub fn from_json(json: &str) -> Result<Self, KeystoreError> {
+        serde_json::from_str(json).map_err(|e| KeystoreError::InvalidJson(e.to_string()))
+    }
+
+    /// Export the Keystore as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Keystore serialization is infallible")
+    }
+
+    /// The hex encoded PublicKey of the encrypted SecretKey.
+    pub fn pubkey(&self) -> &str {
+        &self.pubkey
+    }
+
+    /// The EIP-2334 path the SecretKey was derived at, may be empty.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The UUID of the Keystore.
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    /// A human readable description of the Keystore.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Set the human readable description of the Keystore.
+    pub fn set_description(&mut self, description: &str) {
+        self.description = description.to_string();
+    }
+}
+
+// Convert a password to bytes: NFKD, remove control codes, UTF-8 encode.
+// https://eips.ethereum.org/EIPS/eip-2335#password-requirements
+fn process_password(password: &str) -> Vec<u8> {
+    password
+        .nfkd()
+        .filter(|c| !matches!(*c as u32, 0x00..=0x1F | 0x7F..=0x9F))
+        .collect::<String>()
+        .into_bytes()
+}
+
+// Derive the decryption key from a password using the KDF module parameters.
+fn derive_decryption_key(password: &str, params: &KdfParams) -> Result<Vec<u8>, KeystoreError> {
+    let mut password = process_password(password);
+    let mut decryption_key = vec![0u8; DKLEN as usize];
+
+    let result = match params {
+        KdfParams::Scrypt { dklen, n, p, r, salt } => {
+            if *dklen != DKLEN
+                || *n < 2
+                || !n.is_power_of_two()
+                || n.trailing_zeros() > MAX_SCRYPT_LOG_N as u32
+                || *r > MAX_SCRYPT_R
+                || *p > MAX_SCRYPT_P
+            {
+                Err(KeystoreError::InvalidKdfParams)
+            } else {
+                let log_n = n.trailing_zeros() as u8;
+                scrypt::Params::new(log_n, *r, *p, DKLEN as usize)
+                    .map_err(|_| KeystoreError::InvalidKdfParams)
+                    .and_then(|params| {
+                        scrypt::scrypt(&password, &decode_hex(salt)?, &params, &mut decryption_key)
+                            .map_err(|_| KeystoreError::InvalidKdfParams)
+                    })
+            }
+        }
+        KdfParams::Pbkdf2 { dklen, c, prf, salt } => {
+            if *dklen != DKLEN || *c == 0 || *c > MAX_PBKDF2_C || prf != "hmac-sha256" {
+                Err(KeystoreError::InvalidKdfParams)
+            } else {
+                decode_hex(salt).map(|salt| {
+                    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(&password, &salt, *c, &mut decryption_key)
+                })
+            }
+        }
+    };
+    password.zeroize();
+
+    match result {
+        Ok(()) => Ok(decryption_key),
+        Err(e) => {
+            decryption_key.zeroize();
+            Err(e)
+        }
+    }
+}
+
+// SHA256(DK[16..32] || cipher_message)
+fn checksum(decryption_key: &[u8], cipher_message: &[u8]) -> [u8; 32] {
+    let mut hash256 = HASH256::new();
+    hash256.process_array(&decryption_key[16..32]);
+    hash256.process_array(cipher_message);
+    hash256.hash()
+}
+
+// Compare bytes without branching on their contents, only the lengths are leaked.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// Encrypt or decrypt in place with AES-128-CTR.
+fn aes_128_ctr(key: &[u8], iv: &[u8], data: &mut [u8]) -> Result<(), KeystoreError> {
+    let mut cipher =
+        Aes128Ctr::new_from_slices(key, iv).map_err(|_| KeystoreError::InvalidCipherParams)?;
+    cipher.apply_keystream(data);
+    Ok(())
+}
+
+fn decode_hex(input: &str) -> Result<Vec<u8>, KeystoreError> {
+    hex::decode(input).map_err(|_| KeystoreError::InvalidHex)
+}
+
+// Format random bytes as a version 4 UUID.
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    format!(
+        "{}-{}-{}-{}-{}",
+        hex::encode(&bytes[0..4]),
+        hex::encode(&bytes[4..6]),
+        hex::encode(&bytes[6..8]),
+        hex::encode(&bytes[8..10]),
+        hex::encode(&bytes[10..16])
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vectors from https://eips.ethereum.org/EIPS/eip-2335#test-cases
+    const PASSWORD: &str = "𝔱𝔢𝔰𝔱𝔭𝔞𝔰𝔰𝔴𝔬𝔯𝔡🔑";
+    const SECRET: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+    const PUBKEY: &str = "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07";
+
+    const SCRYPT_KEYSTORE: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "scrypt",
+                "params": {
+                    "dklen": 32,
+                    "n": 262144,
+                    "p": 1,
+                    "r": 8,
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "d2217fe5f3e9a1e34581ef8a78f7c9928e436d36dacc5e846690a5581e8ea484"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {
+                    "iv": "264daa3f303d7259501c93d997d84fe6"
+                },
+                "message": "06ae90d55fe0a6e9c5c3bc5b170827b2e5cce3929ed3f116c2811e6366dfe20f"
+            }
+        },
+        "description": "This is a test keystore that uses scrypt to secure the secret.",
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/3141592653/589793238",
+        "uuid": "1d85ae20-35c5-4611-98e8-aa14a633906f",
+        "version": 4
+    }"#;
+
+    const PBKDF2_KEYSTORE: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "pbkdf2",
+                "params": {
+                    "dklen": 32,
+                    "c": 262144,
+                    "prf": "hmac-sha256",
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {
+                    "iv": "264daa3f303d7259501c93d997d84fe6"
+                },
+                "message": "cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad"
+            }
+        },
+        "description": "This is a test keystore that uses PBKDF2 to secure the secret.",
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/0/0",
+        "uuid": "64625def-3331-4eea-ab6f-782f3ed16a83",
+        "version": 4
+    }"#;
+
+    // Cheap KDF parameters for tests which don't use the reference keystores.
+    const TEST_KDF: Kdf = Kdf::Scrypt { n: 16, r: 8, p: 1 };
+
+    #[test]
+    fn test_decrypt_reference_keystores() {
+        for json in &[SCRYPT_KEYSTORE, PBKDF2_KEYSTORE] {
+            let keystore = Keystore::from_json(json).unwrap();
+            let keypair = keystore.decrypt(PASSWORD).unwrap();
+
+            assert_eq!(keypair.sk.as_bytes().to_vec(), hex::decode(SECRET).unwrap());
+            assert_eq!(keypair.pk.as_bytes().to_vec(), hex::decode(PUBKEY).unwrap());
+            assert_eq!(keystore.pubkey(), PUBKEY);
+        }
+    }
+
+    #[test]
+    fn test_encrypt_reference_keystores() {
+        let sk = SecretKey::from_bytes(&hex::decode(SECRET).unwrap()).unwrap();
+        let salt = hex::decode("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
+            .unwrap();
+        let iv = hex::decode("264daa3f303d7259501c93d997d84fe6").unwrap();
+
+        let vectors = vec![
+            (SCRYPT_KEYSTORE, Kdf::scrypt(), "m/12381/60/3141592653/589793238"),
+            (PBKDF2_KEYSTORE, Kdf::pbkdf2(), "m/12381/60/0/0"),
+        ];
+        for (json, kdf, path) in vectors {
+            let expected = Keystore::from_json(json).unwrap();
+            let mut keystore = Keystore::encrypt_with_params(
+                &sk,
+                PASSWORD,
+                path,
+                kdf,
+                &salt,
+                &iv,
+                expected.uuid(),
+            )
+            .unwrap();
+            keystore.set_description(expected.description());
+            assert_eq!(keystore, expected);
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let keystore = Keystore::from_json(SCRYPT_KEYSTORE).unwrap();
+        let json = keystore.to_json();
+        assert_eq!(Keystore::from_json(&json).unwrap(), keystore);
+
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["crypto"]["kdf"]["function"], "scrypt");
+        assert_eq!(json["crypto"]["kdf"]["params"]["n"], 262144);
+        assert_eq!(json["crypto"]["checksum"]["params"], serde_json::json!({}));
+        assert_eq!(json["version"], 4);
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let mut rng = rand::thread_rng();
+        let keypair = Keypair::random(&mut rng);
+
+        for kdf in &[TEST_KDF, Kdf::Pbkdf2 { c: 16 }] {
+            let keystore =
+                Keystore::encrypt(&keypair.sk, "password", "m/12381/3600/0/0/0", *kdf, &mut rng)
+                    .unwrap();
+            assert_eq!(keystore.path(), "m/12381/3600/0/0/0");
+            assert_eq!(keystore.pubkey(), hex::encode(keypair.pk.as_bytes()));
+
+            let json = keystore.to_json();
+            let decrypted = Keystore::from_json(&json).unwrap().decrypt("password").unwrap();
+            assert_eq!(decrypted, keypair);
+        }
+    }
+
+    #[test]
+    fn test_decrypt_invalid_password() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let keystore =
+            Keystore::encrypt(&keypair.sk, "password", "", TEST_KDF, &mut rand::thread_rng())
+                .unwrap();
+
+        assert_eq!(keystore.decrypt("Password"), Err(KeystoreError::InvalidPassword));
+        assert_eq!(keystore.decrypt(""), Err(KeystoreError::InvalidPassword));
+
+        let error: Box<dyn std::error::Error> = Box::new(KeystoreError::InvalidPassword);
+        assert_eq!(error.to_string(), "checksum mismatch, invalid password");
+    }
+
+    #[test]
+    fn test_decrypt_public_key_mismatch() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let mut keystore =
+            Keystore::encrypt(&keypair.sk, "password", "", TEST_KDF, &mut rand::thread_rng())
+                .unwrap();
+        keystore.pubkey = PUBKEY.to_string();
+
+        assert_eq!(keystore.decrypt("password"), Err(KeystoreError::PublicKeyMismatch));
+    }
+
+    #[test]
+    fn test_decrypt_invalid_keystores() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let keystore =
+            Keystore::encrypt(&keypair.sk, "password", "", TEST_KDF, &mut rand::thread_rng())
+                .unwrap();
+
+        let mut invalid = keystore.clone();
+        invalid.version = 3;
+        assert_eq!(invalid.decrypt("password"), Err(KeystoreError::InvalidVersion(3)));
+
+        let mut invalid = keystore.clone();
+        invalid.crypto.cipher.function = "aes-256-gcm".to_string();
+        assert_eq!(
+            invalid.decrypt("password"),
+            Err(KeystoreError::UnsupportedFunction("aes-256-gcm".to_string()))
+        );
+
+        let mut invalid = keystore.clone();
+        invalid.crypto.kdf.params = KdfParams::Scrypt {
+            dklen: DKLEN,
+            n: 15,
+            p: 1,
+            r: 8,
+            salt: hex::encode([0u8; SALT_BYTES]),
+        };
+        assert_eq!(invalid.decrypt("password"), Err(KeystoreError::InvalidKdfParams));
+
+        // Work factors which would exhaust memory or time are rejected before deriving
+        for (n, r, p) in &[(1 << 31, 8, 1), (1 << 21, 1, 1), (16, 17, 1), (16, 8, 17)] {
+            let mut invalid = keystore.clone();
+            invalid.crypto.kdf.params = KdfParams::Scrypt {
+                dklen: DKLEN,
+                n: *n,
+                p: *p,
+                r: *r,
+                salt: hex::encode([0u8; SALT_BYTES]),
+            };
+            assert_eq!(invalid.decrypt("password"), Err(KeystoreError::InvalidKdfParams));
+        }
+        let mut invalid = keystore.clone();
+        invalid.crypto.kdf.params = KdfParams::Pbkdf2 {
+            dklen: DKLEN,
+            c: MAX_PBKDF2_C + 1,
+            prf: "hmac-sha256".to_string(),
+            salt: hex::encode([0u8; SALT_BYTES]),
+        };
+        assert_eq!(invalid.decrypt("password"), Err(KeystoreError::InvalidKdfParams));
+
+        let mut invalid = keystore.clone();
+        invalid.crypto.cipher.params.iv = hex::encode([0u8; IV_BYTES - 1]);
+        assert_eq!(invalid.decrypt("password"), Err(KeystoreError::InvalidCipherParams));
+
+        let mut invalid = keystore.clone();
+        invalid.crypto.cipher.message = "zz".to_string();
+        assert_eq!(invalid.decrypt("password"), Err(KeystoreError::InvalidHex));
+
+        let json = keystore.to_json().replace("\"scrypt\"", "\"argon2\"");
+        assert!(matches!(Keystore::from_json(&json), Err(KeystoreError::InvalidJson(_))));
+    }
+
+    #[test]
+    fn test_process_password() {
+        // EIP-2335 test password is NFKD normalised to "testpassword🔑"
+        assert_eq!(process_password(PASSWORD), "testpassword🔑".as_bytes());
+
+        // Control codes are removed
+        assert_eq!(process_password("a\u{0}b\u{1f}c\u{7f}d\u{80}e\u{9f}f\u{a0}"), b"abcdef ");
+    }
+
+    #[test]
+    fn test_uuid_v4() {
+        let uuid = uuid_v4([0xff; 16]);
+        assert_eq!(uuid, "ffffffff-ffff-4fff-bfff-ffffffffffff");
+        let uuid = uuid_v4([0; 16]);
+        assert_eq!(uuid, "00000000-0000-4000-8000-000000000000");
+    }
+
+    #[test]
+    fn test_readme() {
+        // This is an exact replica of the README.md at the top level.
+        let keypair = Keypair::random(&mut rand::thread_rng());
+
+        // Encrypt the SecretKey then export the keystore as JSON.
+        let keystore = Keystore::encrypt(
+            &keypair.sk,
+            "password",
+            "m/12381/3600/0/0/0",
+            Kdf::scrypt(),
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+        let json = keystore.to_json();
+
+        // Import the keystore and decrypt the SecretKey.
+        let keystore = Keystore::from_json(&json).unwrap();
+        let decrypted = keystore.decrypt("password").unwrap();
+        assert_eq!(decrypted.pk, keypair.pk);
+    }
+}
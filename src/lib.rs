@@ -11,12 +11,55 @@ extern crate rand;
 
 mod aggregates;
 mod amcl_utils;
+mod cache;
+mod ciphersuite;
+mod compressed;
+mod derivation;
+mod dkg;
+mod error;
+mod hashed_message;
+mod hex_impls;
 mod keys;
+#[cfg(feature = "keystore")]
+mod keystore;
+#[cfg(feature = "mnemonic")]
+mod mnemonic;
+mod proof_of_possession;
+#[cfg(feature = "serde")]
+mod serde_impls;
 mod signature;
+#[cfg(feature = "ssz")]
+mod ssz;
+mod threshold;
 
 use self::amcl::bls381 as BLSCurve;
 
-pub use aggregates::{AggregatePublicKey, AggregateSignature};
-pub use amcl_utils::{AmclError, G1_BYTES, G2_BYTES, SECRET_KEY_BYTES};
-pub use keys::{Keypair, PublicKey, SecretKey};
-pub use signature::Signature;
+pub use aggregates::{
+    AggregatePublicKey, AggregateSignature, MinSigAggregatePublicKey, MinSigAggregateSignature,
+    DEFAULT_RAND_BITS, MAX_RAND_BITS, MIN_RAND_BITS,
+};
+pub use amcl_utils::{msm_g1, msm_g2, AmclError, G1_BYTES, G2_BYTES, SECRET_KEY_BYTES};
+pub use cache::PublicKeyCache;
+pub use ciphersuite::{
+    BasicScheme, Ciphersuite, MessageAugmentationScheme, ProofOfPossessionScheme,
+};
+pub use compressed::{PublicKeyBytes, SignatureBytes};
+pub use derivation::{
+    derive_child_sk, derive_master_sk, derive_path, DerivationPath, DerivationPathError,
+};
+pub use dkg::{Dkg, DkgError, DkgMessage, DkgOutput};
+pub use error::{Error, VerifyError};
+pub use hashed_message::HashedMessage;
+pub use hex_impls::ParseHexError;
+pub use keys::{Keypair, MinSigPublicKey, PreparedPublicKey, PublicKey, SecretKey, VerifyingKey};
+#[cfg(feature = "keystore")]
+pub use keystore::{Kdf, Keystore, KeystoreError};
+#[cfg(feature = "mnemonic")]
+pub use mnemonic::{Mnemonic, MnemonicError};
+pub use proof_of_possession::{MinSigProofOfPossession, ProofOfPossession};
+#[cfg(feature = "serde")]
+pub use serde_impls::serialize_secret_key;
+pub use signature::{MinSigSignature, Signature};
+#[cfg(feature = "ssz")]
+pub use ssz::{Ssz, BYTES_PER_CHUNK};
+pub use threshold::{public_key_share, verify_share, ThresholdError, VerifiableShares};
@@ -0,0 +1,419 @@
+extern crate amcl;
+extern crate pbkdf2;
+extern crate rand;
+extern crate sha2;
+extern crate unicode_normalization;
+extern crate zeroize;
+
+use self::unicode_normalization::UnicodeNormalization;
+use self::zeroize::Zeroize;
+use super::derivation::{derive_master_sk, derive_path, DerivationPath};
+use super::error::Error;
+use super::keys::SecretKey;
+
+use amcl::hash256::HASH256;
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+// Mnemonic Constants
+/// Number of PBKDF2-HMAC-SHA512 iterations when converting a mnemonic to a seed.
+pub const PBKDF2_ROUNDS: u32 = 2048;
+/// Length in bytes of the seed of a mnemonic.
+pub const SEED_BYTES: usize = 64;
+/// Salt prefix of the passphrase when converting a mnemonic to a seed.
+pub const SEED_SALT: &str = "mnemonic";
+
+const WORD_BITS: usize = 11;
+
+lazy_static! {
+    /// The BIP-39 English wordlist, sorted.
+    static ref ENGLISH: Vec<&'static str> = include_str!("wordlists/english.txt").lines().collect();
+}
+
+/// Errors when parsing or generating a Mnemonic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MnemonicError {
+    /// Mnemonics MUST have 12, 15, 18, 21 or 24 words.
+    InvalidWordCount(usize),
+    /// The word at this position is not in the English wordlist.
+    UnknownWord(usize),
+    /// The checksum bits of the mnemonic do not match the entropy.
+    InvalidChecksum,
+    /// Entropy MUST be 16, 20, 24, 28 or 32 bytes.
+    InvalidEntropyLength(usize),
+}
+
+impl fmt::Display for MnemonicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MnemonicError::InvalidWordCount(count) => {
+                write!(f, "expected 12, 15, 18, 21 or 24 words, found {}", count)
+            }
+            MnemonicError::UnknownWord(position) => {
+                write!(f, "word at position {} is not in the English wordlist", position)
+            }
+            MnemonicError::InvalidChecksum => write!(f, "invalid mnemonic checksum"),
+            MnemonicError::InvalidEntropyLength(len) => {
+                write!(f, "expected 16, 20, 24, 28 or 32 bytes of entropy, found {}", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MnemonicError {}
+
+/// A BIP-39 mnemonic phrase using the English wordlist.
+///
+/// https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    phrase: String,
+    entropy: Vec<u8>,
+}
+
+impl Mnemonic {
+    /// Generate a new Mnemonic of `word_count` words from an Rng.
+    pub fn generate<R: Rng + ?Sized>(
+        rng: &mut R,
+        word_count: usize,
+    ) -> Result<Self, MnemonicError> {
+        if !is_valid_word_count(word_count) {
+            return Err(MnemonicError::InvalidWordCount(word_count));
+        }
+
+        // ENT = 32 * MS / 3 bits
+        let mut entropy = vec![0u8; word_count * 4 / 3];
+        rng.fill(&mut entropy[..]);
+        let mnemonic = Self::from_entropy(&entropy);
+        entropy.zeroize();
+        mnemonic
+    }
+
+    /// Instantiate a Mnemonic from 16, 20, 24, 28 or 32 bytes of entropy.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        if !(16..=32).contains(&entropy.len()) || entropy.len() % 4 != 0 {
+            return Err(MnemonicError::InvalidEntropyLength(entropy.len()));
+        }
+
+        // bits = entropy || SHA256(entropy)[0..CS] where CS = ENT / 32
+        let checksum = sha256(entropy);
+        let word_count = entropy.len() * 3 / 4;
+        let bit = |i: usize| {
+            let byte = if i < entropy.len() * 8 { entropy[i / 8] } else { checksum[0] };
+            (byte >> (7 - i % 8)) & 1
+        };
+
+        let words: Vec<&str> = (0..word_count)
+            .map(|w| {
+                let index = (0..WORD_BITS)
+                    .fold(0usize, |index, b| (index << 1) | bit(w * WORD_BITS + b) as usize);
+                ENGLISH[index]
+            })
+            .collect();
+
+        Ok(Self { phrase: words.join(" "), entropy: entropy.to_vec() })
+    }
+
+    /// Parse and validate a Mnemonic phrase, verifying its checksum.
+    pub fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+        let mut normalized: String = phrase.nfkd().collect();
+        let indices = normalized
+            .split_whitespace()
+            .enumerate()
+            .map(|(position, word)| {
+                ENGLISH.binary_search(&word).map_err(|_| MnemonicError::UnknownWord(position))
+            })
+            .collect::<Result<Vec<usize>, _>>();
+        normalized.zeroize();
+        let mut indices = indices?;
+
+        let word_count = indices.len();
+        if !is_valid_word_count(word_count) {
+            return Err(MnemonicError::InvalidWordCount(word_count));
+        }
+
+        // Unpack the 11 bit word indices into entropy || checksum
+        let mut bits = vec![0u8; (word_count * WORD_BITS).div_ceil(8)];
+        for (w, index) in indices.iter().enumerate() {
+            for b in 0..WORD_BITS {
+                if (index >> (WORD_BITS - 1 - b)) & 1 == 1 {
+                    let i = w * WORD_BITS + b;
+                    bits[i / 8] |= 1 << (7 - i % 8);
+                }
+            }
+        }
+        indices.zeroize();
+
+        let entropy_len = word_count * 4 / 3;
+        let checksum_bits = entropy_len / 4;
+        let mnemonic = Self::from_entropy(&bits[..entropy_len]);
+        let checksum = bits[entropy_len] >> (8 - checksum_bits);
+        bits.zeroize();
+
+        let mnemonic = mnemonic?;
+        if sha256(&mnemonic.entropy)[0] >> (8 - checksum_bits) != checksum {
+            return Err(MnemonicError::InvalidChecksum);
+        }
+        Ok(mnemonic)
+    }
+
+    /// The phrase of the Mnemonic, words are separated by a single space.
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// The entropy encoded by the Mnemonic.
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Convert the Mnemonic and an optional passphrase to a 64 byte seed.
+    ///
+    /// seed = PBKDF2-HMAC-SHA512(NFKD(phrase), "mnemonic" || NFKD(passphrase), 2048)
+    pub fn to_seed(&self, passphrase: &str) -> [u8; SEED_BYTES] {
+        let mut salt: String = SEED_SALT.chars().chain(passphrase.nfkd()).collect();
+        let mut seed = [0u8; SEED_BYTES];
+        pbkdf2::pbkdf2_hmac::<sha2::Sha512>(
+            self.phrase.as_bytes(),
+            salt.as_bytes(),
+            PBKDF2_ROUNDS,
+            &mut seed,
+        );
+        salt.zeroize();
+        seed
+    }
+
+    /// Derive the EIP-2333 master SecretKey of the Mnemonic's seed.
+    pub fn master_sk(&self, passphrase: &str) -> SecretKey {
+        let mut seed = self.to_seed(passphrase);
+        let sk = derive_master_sk(&seed).unwrap(); // will only error if seed < 32 bytes
+        seed.zeroize();
+        sk
+    }
+
+    /// Derive the SecretKey at an EIP-2334 DerivationPath of the Mnemonic's seed.
+    pub fn derive_path(&self, passphrase: &str, path: &DerivationPath) -> SecretKey {
+        let mut seed = self.to_seed(passphrase);
+        let sk = derive_path(&seed, path).unwrap(); // will only error if seed < 32 bytes
+        seed.zeroize();
+        sk
+    }
+
+    /// Generate a SecretKey using the Mnemonic's seed as the IKM of `SecretKey::key_generate`.
+    pub fn key_generate(&self, passphrase: &str, key_info: &[u8]) -> Result<SecretKey, Error> {
+        let mut seed = self.to_seed(passphrase);
+        let sk = SecretKey::key_generate(&seed, key_info);
+        seed.zeroize();
+        sk
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = MnemonicError;
+
+    fn from_str(phrase: &str) -> Result<Self, Self::Err> {
+        Self::from_phrase(phrase)
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.phrase)
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mnemonic(<redacted>)")
+    }
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.phrase.zeroize();
+        self.entropy.zeroize();
+    }
+}
+
+fn is_valid_word_count(word_count: usize) -> bool {
+    (12..=24).contains(&word_count) && word_count % 3 == 0
+}
+
+fn sha256(input: &[u8]) -> [u8; 32] {
+    let mut hash256 = HASH256::new();
+    hash256.process_array(input);
+    hash256.hash()
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate hex;
+
+    use super::*;
+
+    // Test vectors from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+    // (entropy, mnemonic, seed) with the passphrase "TREZOR".
+    const TEST_VECTORS: &[(&str, &str, &str)] = &[
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+        (
+            "80808080808080808080808080808080",
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+            "d71de856f81a8acc65e6fc851a38d4d7ec216fd0796d0a6827a3ad6ed5511a30fa280f12eb2e47ed2ac03b5c462a0358d18d69fe4f985ec81778c1b370b652a8",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+            "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069",
+        ),
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+            "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+        ),
+        (
+            "9e885d952ad362caeb4efe34a8e91bd2",
+            "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+            "274ddc525802f7c828d8ef7ddbcdc5304e87ac3535913611fbbfa986d0c9e5476c91689f9c8a54fd55bd38606aa6a8595ad213d4c9c9f9aca3fb217069a41028",
+        ),
+        (
+            "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+            "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length",
+            "64c87cde7e12ecf6704ab95bb1408bef047c22db4cc7491c4271d170a1b213d20b385bc1588d9c7b38f1b39d415665b8a9030c9ec653d75e65f847d8fc1fc440",
+        ),
+    ];
+
+    #[test]
+    fn test_wordlist() {
+        assert_eq!(ENGLISH.len(), 2048);
+        assert_eq!(ENGLISH[0], "abandon");
+        assert_eq!(ENGLISH[2047], "zoo");
+        assert!(ENGLISH.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_bip39_vectors() {
+        for (entropy, phrase, seed) in TEST_VECTORS {
+            let entropy = hex::decode(entropy).unwrap();
+
+            let mnemonic = Mnemonic::from_entropy(&entropy).unwrap();
+            assert_eq!(mnemonic.phrase(), *phrase);
+
+            let mnemonic = Mnemonic::from_phrase(phrase).unwrap();
+            assert_eq!(mnemonic.entropy(), &entropy[..]);
+            assert_eq!(mnemonic.to_seed("TREZOR").to_vec(), hex::decode(seed).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_eip2333_master_sk() {
+        // The seed of the first vector is the seed of the first EIP-2333 test case.
+        let mnemonic = Mnemonic::from_phrase(TEST_VECTORS[0].1).unwrap();
+        let master_sk = mnemonic.master_sk("TREZOR");
+        assert_eq!(
+            master_sk.as_bytes().to_vec(),
+            hex::decode("0d7359d57963ab8fbbde1852dcf553fedbc31f464d80ee7d40ae683122b45070")
+                .unwrap()
+        );
+
+        let path = DerivationPath::new(&[0]);
+        assert_eq!(
+            mnemonic.derive_path("TREZOR", &path).as_bytes().to_vec(),
+            hex::decode("2d18bd6c14e6d15bf8b5085c9b74f3daae3b03cc2014770a599d8c1539e50f8e")
+                .unwrap()
+        );
+
+        let seed = mnemonic.to_seed("TREZOR");
+        assert_eq!(mnemonic.key_generate("TREZOR", &[]), SecretKey::key_generate(&seed, &[]));
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut rng = rand::thread_rng();
+        for word_count in &[12, 15, 18, 21, 24] {
+            let mnemonic = Mnemonic::generate(&mut rng, *word_count).unwrap();
+            assert_eq!(mnemonic.phrase().split(' ').count(), *word_count);
+            assert_eq!(mnemonic.entropy().len(), word_count * 4 / 3);
+            assert_eq!(mnemonic.phrase().parse::<Mnemonic>().unwrap(), mnemonic);
+        }
+
+        for word_count in &[0, 11, 13, 23, 25, 27] {
+            assert_eq!(
+                Mnemonic::generate(&mut rng, *word_count),
+                Err(MnemonicError::InvalidWordCount(*word_count))
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_phrase_invalid() {
+        // Checksum of "about" is valid, "abandon" is not
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert_eq!(Mnemonic::from_phrase(phrase), Err(MnemonicError::InvalidChecksum));
+
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert_eq!(Mnemonic::from_phrase(phrase), Err(MnemonicError::InvalidWordCount(11)));
+
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon bitcoin about";
+        assert_eq!(Mnemonic::from_phrase(phrase), Err(MnemonicError::UnknownWord(10)));
+
+        let phrase = "Abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_eq!(Mnemonic::from_phrase(phrase), Err(MnemonicError::UnknownWord(0)));
+
+        assert_eq!(Mnemonic::from_phrase(""), Err(MnemonicError::InvalidWordCount(0)));
+
+        let error: Box<dyn std::error::Error> = Box::new(MnemonicError::UnknownWord(0));
+        assert_eq!(error.to_string(), "word at position 0 is not in the English wordlist");
+    }
+
+    #[test]
+    fn test_from_phrase_whitespace() {
+        let phrase =
+            "  legal winner thank year wave sausage\tworth useful legal winner thank   yellow \n";
+        let mnemonic = Mnemonic::from_phrase(phrase).unwrap();
+        assert_eq!(mnemonic.phrase(), TEST_VECTORS[1].1);
+        assert_eq!(mnemonic.to_string(), TEST_VECTORS[1].1);
+    }
+
+    #[test]
+    fn test_from_entropy_invalid() {
+        for len in &[0, 12, 15, 17, 33, 36] {
+            assert_eq!(
+                Mnemonic::from_entropy(&vec![0u8; *len]),
+                Err(MnemonicError::InvalidEntropyLength(*len))
+            );
+        }
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let mnemonic = Mnemonic::from_phrase(TEST_VECTORS[1].1).unwrap();
+        assert_eq!(format!("{:?}", mnemonic), "Mnemonic(<redacted>)");
+    }
+
+    #[test]
+    fn test_readme() {
+        // This is an exact replica of the README.md at the top level.
+        // Generate a new 24 word mnemonic.
+        let mnemonic = Mnemonic::generate(&mut rand::thread_rng(), 24).unwrap();
+
+        // Recover the mnemonic from its phrase, verifying the checksum.
+        let mnemonic = Mnemonic::from_phrase(mnemonic.phrase()).unwrap();
+
+        // Derive the signing key of validator 0.
+        let signing_sk = mnemonic.derive_path("", &DerivationPath::signing(0));
+        assert_eq!(
+            signing_sk,
+            SecretKey::derive_path(&mnemonic.to_seed(""), &DerivationPath::signing(0)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,277 @@
+extern crate amcl;
+
+use super::amcl_utils::{
+    self, ate2_evaluation, compress_g1, compress_g2, decompress_g1, decompress_g2, g1mul, g2mul,
+    hash_public_key_to_g1, hash_public_key_to_g2, subgroup_check_g1, subgroup_check_g2, GroupG1,
+    GroupG2, G1_BYTES, G2_BYTES,
+};
+use super::error::Error;
+use super::keys::{MinSigPublicKey, PublicKey, SecretKey};
+
+/// A BLS Proof of Possession of a SecretKey.
+///
+/// Proves that the holder of a PublicKey also holds the corresponding SecretKey, protecting
+/// aggregate signatures against rogue key attacks.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct ProofOfPossession {
+    pub point: GroupG2,
+}
+
+impl ProofOfPossession {
+    /// PopProve
+    ///
+    /// Instantiate a new ProofOfPossession for the PublicKey of a SecretKey.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3.3.2
+    pub fn new(sk: &SecretKey) -> Self {
+        let pk = PublicKey::from_secret_key(sk);
+        let hash_point = hash_public_key_to_g2(&pk.as_bytes());
+        let proof = g2mul(&hash_point, sk.as_raw());
+        Self { point: proof }
+    }
+
+    /// PopVerify
+    ///
+    /// Verifies the ProofOfPossession against a PublicKey.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3.3.3
+    pub fn verify(&self, pk: &PublicKey) -> bool {
+        // Proof Subgroup checks
+        if !subgroup_check_g2(&self.point) {
+            return false;
+        }
+
+        // PublicKey must be valid
+        if !pk.key_validate() {
+            return false;
+        }
+
+        let mut pk_hash_point = hash_public_key_to_g2(&pk.as_bytes());
+        pk_hash_point.affine();
+
+        // Faster ate2 evaualtion checks e(R, -G1) * e(H(PK), PK) == 1
+        let mut generator_g1_negative = amcl_utils::GroupG1::generator();
+        generator_g1_negative.neg();
+        ate2_evaluation(&self.point, &generator_g1_negative, &pk_hash_point, &pk.point)
+    }
+
+    /// Instantiate a ProofOfPossession from compressed bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ProofOfPossession, Error> {
+        let point = decompress_g2(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// Compress the ProofOfPossession as bytes.
+    pub fn as_bytes(&self) -> [u8; G2_BYTES] {
+        compress_g2(&self.point)
+    }
+}
+
+/// A BLS Proof of Possession of a SecretKey for the minimal-signature-size variant.
+///
+/// The proof is a point in G1 over the MinSigPublicKey, using the
+/// BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_ ciphersuite.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MinSigProofOfPossession {
+    pub point: GroupG1,
+}
+
+impl MinSigProofOfPossession {
+    /// PopProve
+    ///
+    /// Instantiate a new MinSigProofOfPossession for the MinSigPublicKey of a SecretKey.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3.3.2
+    pub fn new(sk: &SecretKey) -> Self {
+        let pk = MinSigPublicKey::from_secret_key(sk);
+        let hash_point = hash_public_key_to_g1(&pk.as_bytes());
+        let proof = g1mul(&hash_point, sk.as_raw());
+        Self { point: proof }
+    }
+
+    /// PopVerify
+    ///
+    /// Verifies the MinSigProofOfPossession against a MinSigPublicKey.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3.3.3
+    pub fn verify(&self, pk: &MinSigPublicKey) -> bool {
+        // Proof Subgroup checks
+        if !subgroup_check_g1(&self.point) {
+            return false;
+        }
+
+        // PublicKey must be valid
+        if !pk.key_validate() {
+            return false;
+        }
+
+        let mut pk_hash_point = hash_public_key_to_g1(&pk.as_bytes());
+        pk_hash_point.affine();
+        let mut pk_point = pk.point.clone();
+        pk_point.affine();
+
+        // Faster ate2 evaualtion checks e(PK, H(PK)) * e(-G2, R) == 1
+        let mut generator_g2_negative = amcl_utils::GroupG2::generator();
+        generator_g2_negative.neg();
+        ate2_evaluation(&pk_point, &pk_hash_point, &generator_g2_negative, &self.point)
+    }
+
+    /// Instantiate a MinSigProofOfPossession from compressed bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<MinSigProofOfPossession, Error> {
+        let point = decompress_g1(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// Compress the MinSigProofOfPossession as bytes.
+    pub fn as_bytes(&self) -> [u8; G1_BYTES] {
+        compress_g1(&self.point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate hex;
+    extern crate rand;
+
+    use super::super::keys::Keypair;
+    use super::super::signature::{MinSigSignature, Signature};
+    use super::*;
+    use BLSCurve::bls381::proof_of_possession::{pop_prove_g1, pop_verify_g1};
+
+    #[test]
+    fn test_pop_prove_verify() {
+        for _ in 0..5 {
+            let keypair = Keypair::random(&mut rand::thread_rng());
+
+            let proof = keypair.sk.pop_prove();
+            assert!(keypair.pk.pop_verify(&proof));
+
+            // Test serializing, then deserializing the proof
+            let proof_bytes = proof.as_bytes();
+            let new_proof = ProofOfPossession::from_bytes(&proof_bytes).unwrap();
+            assert_eq!(proof, new_proof);
+            assert!(keypair.pk.pop_verify(&new_proof));
+        }
+    }
+
+    #[test]
+    fn test_pop_known_vectors() {
+        // (SecretKey, PublicKey, ProofOfPossession) generated using the
+        // BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_ ciphersuite of blst.
+        let vectors = vec![
+            (
+                "4efc7a7e20004b59fc1f2a82fe58065a8aca87c2e975b54b60ee4f64ed3b8c6f",
+                "b60d2086e39ce697d6d7b2414e4a67f115a61bc6b2437d3a0587fadb0ded91e380a1acf423b98716e131fcb597035524",
+                "b8f0202ec49b035365cfefad57efd0336c1a61da76c9ea91ac66a0ab107cd14c96c7b0cf5810fcda660e7c284ccfa78812c3dab2ffd9cf07a8e821619e75d50f28a5d1b6d637e34a759e3b4a347a400bec3ad1d5be53264fef16aba410ab29bd",
+            ),
+            (
+                "263dbd792f5b1be47ed85f8938c0f29586af0d3ac7b977f21c278fe1462040e3",
+                "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+                "b803eb0ed93ea10224a73b6b9c725796be9f5fefd215ef7a5b97234cc956cf6870db6127b7e4d824ec62276078e787db05584ce1adbf076bc0808ca0f15b73d59060254b25393d95dfc7abe3cda566842aaedf50bbb062aae1bbb6ef3b1f77e1",
+            ),
+            (
+                "47b8192d77bf871b62e87859d653922725724a5c031afeabc60bcef5ff665138",
+                "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+                "88bb31b27eae23038e14f9d9d1b628a39f5881b5278c3c6f0249f81ba0deb1f68aa5f8847854d6554051aa810fdf1cdb02df4af7a5647b1aa4afb60ec6d446ee17af24a8a50876ffdaf9bf475038ec5f8ebeda1c1c6a3220293e23b13a9a5d26",
+            ),
+        ];
+
+        for (sk, pk, proof) in vectors {
+            let sk = SecretKey::from_bytes(&hex::decode(sk).unwrap()).unwrap();
+            let pk = PublicKey::from_bytes(&hex::decode(pk).unwrap()).unwrap();
+            let proof_bytes = hex::decode(proof).unwrap();
+
+            assert_eq!(PublicKey::from_secret_key(&sk), pk);
+            assert_eq!(sk.pop_prove().as_bytes().to_vec(), proof_bytes);
+
+            let proof = ProofOfPossession::from_bytes(&proof_bytes).unwrap();
+            assert!(pk.pop_verify(&proof));
+        }
+    }
+
+    #[test]
+    fn test_pop_verify_wrong_public_key() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let other_keypair = Keypair::random(&mut rand::thread_rng());
+
+        let proof = keypair.sk.pop_prove();
+        assert!(!other_keypair.pk.pop_verify(&proof));
+    }
+
+    #[test]
+    fn test_pop_verify_infinity() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let proof = keypair.sk.pop_prove();
+
+        // PublicKey of infinity should fail
+        let pk = PublicKey { point: amcl_utils::GroupG1::new() };
+        assert!(!pk.pop_verify(&proof));
+
+        // Proof of infinity should fail
+        let proof = ProofOfPossession { point: GroupG2::new() };
+        assert!(!keypair.pk.pop_verify(&proof));
+    }
+
+    #[test]
+    fn test_pop_domain_separation() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let pk_bytes = keypair.pk.as_bytes();
+
+        // A Signature over the PublicKey bytes is not a valid ProofOfPossession
+        let signature = Signature::new(&pk_bytes, &keypair.sk);
+        let proof = ProofOfPossession { point: signature.point };
+        assert!(!keypair.pk.pop_verify(&proof));
+
+        // A ProofOfPossession is not a valid Signature over the PublicKey bytes
+        let proof = keypair.sk.pop_prove();
+        let signature = Signature { point: proof.point };
+        assert!(!signature.verify(&pk_bytes, &keypair.pk));
+    }
+
+    #[test]
+    fn test_min_sig_pop_prove_verify() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let pk = MinSigPublicKey::from_secret_key(&keypair.sk);
+
+        let proof = keypair.sk.min_sig_pop_prove();
+        assert!(pk.pop_verify(&proof));
+
+        // Test serializing, then deserializing the proof
+        let proof_bytes = proof.as_bytes();
+        assert_eq!(proof_bytes.len(), G1_BYTES);
+        let new_proof = MinSigProofOfPossession::from_bytes(&proof_bytes).unwrap();
+        assert_eq!(proof, new_proof);
+        assert!(pk.pop_verify(&new_proof));
+
+        // Matches the amcl implementation of the ciphersuite
+        let sk_bytes = keypair.sk.as_bytes();
+        assert_eq!(pop_prove_g1(&sk_bytes).unwrap(), proof_bytes);
+        assert!(pop_verify_g1(&pk.as_bytes(), &proof_bytes));
+
+        // Wrong and infinity PublicKeys, or a proof of infinity, should fail
+        let other_pk =
+            MinSigPublicKey::from_secret_key(&SecretKey::random(&mut rand::thread_rng()));
+        assert!(!other_pk.pop_verify(&proof));
+        let infinity_pk = MinSigPublicKey { point: GroupG2::new() };
+        assert!(!infinity_pk.pop_verify(&proof));
+        assert!(!pk.pop_verify(&MinSigProofOfPossession { point: GroupG1::new() }));
+
+        // A MinSigSignature over the PublicKey bytes is not a valid proof
+        let signature = MinSigSignature::new(&pk.as_bytes(), &keypair.sk);
+        assert!(!pk.pop_verify(&MinSigProofOfPossession { point: signature.point }));
+    }
+
+    #[test]
+    fn test_readme() {
+        // This is an exact replica of the README.md at the top level.
+        let keypair = Keypair::random(&mut rand::thread_rng());
+
+        // Generate a proof for the public key.
+        let proof = keypair.sk.pop_prove();
+
+        // Serialize and de-serialize the proof, just 'cause we can.
+        let proof_bytes = proof.as_bytes();
+        let proof = ProofOfPossession::from_bytes(&proof_bytes).unwrap();
+
+        // Verify the proof before using the public key in any aggregates.
+        assert!(keypair.pk.pop_verify(&proof));
+    }
+}
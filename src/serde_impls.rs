@@ -0,0 +1,237 @@
+extern crate hex;
+extern crate serde;
+extern crate zeroize;
+
+use self::serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use self::serde::ser::{self, Serialize, SerializeTuple, Serializer};
+use self::zeroize::Zeroize;
+use super::aggregates::{AggregatePublicKey, AggregateSignature};
+use super::amcl_utils::{G1_BYTES, G2_BYTES, SECRET_KEY_BYTES};
+use super::keys::{PublicKey, SecretKey};
+use super::signature::Signature;
+use std::fmt;
+
+// Serialize bytes as 0x-prefixed hex for human-readable formats and a fixed-size array otherwise.
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        let mut hex_string = format!("0x{}", hex::encode(bytes));
+        let result = serializer.serialize_str(&hex_string);
+        hex_string.zeroize();
+        result
+    } else {
+        let mut tuple = serializer.serialize_tuple(bytes.len())?;
+        for byte in bytes {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+}
+
+// Deserialize exactly `len` bytes written by `serialize_bytes`.
+fn deserialize_bytes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    len: usize,
+) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor { len })
+    } else {
+        deserializer.deserialize_tuple(len, BytesVisitor { len })
+    }
+}
+
+struct BytesVisitor {
+    len: usize,
+}
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bytes as a 0x-prefixed hex string or an array", self.len)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Vec<u8>, E> {
+        let stripped = match value.strip_prefix("0x") {
+            Some(stripped) => stripped,
+            None => return Err(E::custom("hex string must be 0x-prefixed")),
+        };
+        let bytes = hex::decode(stripped).map_err(E::custom)?;
+        if bytes.len() != self.len {
+            return Err(E::invalid_length(bytes.len(), &self));
+        }
+        Ok(bytes)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(self.len);
+        for i in 0..self.len {
+            match seq.next_element()? {
+                Some(byte) => bytes.push(byte),
+                None => return Err(de::Error::invalid_length(i, &self)),
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+// Implements Serialize with `as_bytes` and Deserialize with the validating `from_bytes`.
+macro_rules! impl_serde {
+    ($type:ident, $len:expr) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes(&self.as_bytes(), serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bytes = deserialize_bytes(deserializer, $len)?;
+                $type::from_bytes(&bytes).map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+impl_serde!(PublicKey, G1_BYTES);
+impl_serde!(AggregatePublicKey, G1_BYTES);
+impl_serde!(Signature, G2_BYTES);
+impl_serde!(AggregateSignature, G2_BYTES);
+
+/// Errors rather than exposing the secret, see `serialize_secret_key`.
+impl Serialize for SecretKey {
+    fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+        Err(ser::Error::custom("SecretKey must be serialized with serialize_secret_key"))
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut bytes = deserialize_bytes(deserializer, SECRET_KEY_BYTES)?;
+        let secret_key = SecretKey::from_bytes(&bytes);
+        bytes.zeroize();
+        secret_key.map_err(de::Error::custom)
+    }
+}
+
+/// Serialize the bytes of a SecretKey, which otherwise fails to serialize.
+///
+/// Opt in with `#[serde(serialize_with = "milagro_bls::serialize_secret_key")]`.
+pub fn serialize_secret_key<S: Serializer>(
+    secret_key: &SecretKey,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut bytes = secret_key.as_bytes();
+    let result = serialize_bytes(&bytes, serializer);
+    bytes.zeroize();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate rand;
+    extern crate serde_cbor;
+    extern crate serde_json;
+
+    use super::super::keys::Keypair;
+    use super::*;
+
+    #[derive(serde::Serialize)]
+    struct ExposedSecretKey<'a> {
+        #[serde(serialize_with = "serialize_secret_key")]
+        sk: &'a SecretKey,
+    }
+
+    #[test]
+    fn test_serde_human_readable() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", &keypair.sk);
+
+        let json = serde_json::to_string(&keypair.pk).unwrap();
+        assert_eq!(json, format!("\"0x{}\"", hex::encode(keypair.pk.as_bytes())));
+        assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), keypair.pk);
+
+        let json = serde_json::to_string(&signature).unwrap();
+        assert_eq!(json, format!("\"0x{}\"", hex::encode(signature.as_bytes())));
+        assert_eq!(serde_json::from_str::<Signature>(&json).unwrap(), signature);
+
+        let aggregate_public_key = AggregatePublicKey::from_public_key(&keypair.pk);
+        let json = serde_json::to_string(&aggregate_public_key).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AggregatePublicKey>(&json).unwrap(),
+            aggregate_public_key
+        );
+
+        let aggregate_signature = AggregateSignature::aggregate(&[&signature]);
+        let json = serde_json::to_string(&aggregate_signature).unwrap();
+        assert_eq!(serde_json::from_str::<AggregateSignature>(&json).unwrap(), aggregate_signature);
+    }
+
+    #[test]
+    fn test_serde_binary() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", &keypair.sk);
+
+        let cbor = serde_cbor::to_vec(&keypair.pk).unwrap();
+        assert_eq!(serde_cbor::from_slice::<PublicKey>(&cbor).unwrap(), keypair.pk);
+        let cbor = serde_cbor::to_vec(&signature).unwrap();
+        assert_eq!(serde_cbor::from_slice::<Signature>(&cbor).unwrap(), signature);
+
+        // Fixed-size arrays of bytes
+        let value: serde_cbor::Value = serde_cbor::from_slice(&cbor).unwrap();
+        let expected =
+            signature.as_bytes().iter().map(|b| serde_cbor::Value::Integer(*b as i128)).collect();
+        assert_eq!(value, serde_cbor::Value::Array(expected));
+    }
+
+    #[test]
+    fn test_serde_secret_key() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+
+        // Not serialized by default
+        assert!(serde_json::to_string(&keypair.sk).is_err());
+        assert!(serde_cbor::to_vec(&keypair.sk).is_err());
+
+        // Exposed when opted in
+        let json = serde_json::to_string(&ExposedSecretKey { sk: &keypair.sk }).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::from_value::<SecretKey>(value["sk"].clone()).unwrap(), keypair.sk);
+
+        let cbor = serde_cbor::to_vec(&ExposedSecretKey { sk: &keypair.sk }).unwrap();
+        let value: serde_cbor::Value = serde_cbor::from_slice(&cbor).unwrap();
+        let sk = match value {
+            serde_cbor::Value::Map(map) => map.values().next().unwrap().clone(),
+            _ => panic!("ExposedSecretKey should serialize as a map"),
+        };
+        assert_eq!(serde_cbor::value::from_value::<SecretKey>(sk).unwrap(), keypair.sk);
+    }
+
+    #[test]
+    fn test_serde_invalid() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let hex_public_key = hex::encode(keypair.pk.as_bytes());
+
+        // Missing prefix, wrong length and invalid hex
+        assert!(serde_json::from_str::<PublicKey>(&format!("\"{}\"", hex_public_key)).is_err());
+        assert!(serde_json::from_str::<PublicKey>(&format!("\"0x{}00\"", hex_public_key)).is_err());
+        assert!(serde_json::from_str::<PublicKey>("\"0xzz\"").is_err());
+        assert!(serde_json::from_str::<Signature>(&format!("\"0x{}\"", hex_public_key)).is_err());
+
+        // Invalid points are rejected by from_bytes
+        let invalid = format!("\"0x{}\"", hex::encode([0xff; G1_BYTES]));
+        assert!(serde_json::from_str::<PublicKey>(&invalid).is_err());
+        let mut infinity = [0u8; G1_BYTES];
+        infinity[0] = 0xc0;
+        let infinity = format!("\"0x{}\"", hex::encode(infinity));
+        assert!(serde_json::from_str::<PublicKey>(&infinity).is_err());
+        // Infinity may be a valid AggregatePublicKey
+        assert!(serde_json::from_str::<AggregatePublicKey>(&infinity).is_ok());
+
+        // SecretKeys must be in range
+        let invalid = format!("\"0x{}\"", hex::encode([0xff; SECRET_KEY_BYTES]));
+        assert!(serde_json::from_str::<SecretKey>(&invalid).is_err());
+
+        // Short arrays
+        let cbor = serde_cbor::to_vec(&vec![0u8; G1_BYTES - 1]).unwrap();
+        assert!(serde_cbor::from_slice::<PublicKey>(&cbor).is_err());
+    }
+}
@@ -1,10 +1,20 @@
 extern crate amcl;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 use super::amcl_utils::{
-    self, ate2_evaluation, compress_g2, decompress_g2, g2mul, hash_to_curve_g2, subgroup_check_g2,
-    AmclError, GroupG2, G2_BYTES,
+    self, ate2_evaluation, compress_g1, compress_g2, decompress_g1, decompress_g2, g1mul, g2mul,
+    hash_to_curve_g1_with_dst, hash_to_curve_g2_with_dst, subgroup_check_g1, subgroup_check_g2,
+    validate_dst, GroupG1, GroupG2, G1_BYTES, G2_BYTES,
+};
+use super::ciphersuite::{
+    hash_message, hash_message_min_sig, Ciphersuite, ProofOfPossessionScheme,
 };
-use super::keys::{PublicKey, SecretKey};
+use super::error::{Error, VerifyError};
+use super::hashed_message::HashedMessage;
+use super::keys::{MinSigPublicKey, PreparedPublicKey, PublicKey, SecretKey, VerifyingKey};
+use super::threshold::{lagrange_coefficients, ThresholdError};
 
 #[derive(Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -14,33 +24,182 @@ pub struct Signature {
 
 impl Signature {
     /// Instantiate a new Signature from a message and a SecretKey.
+    ///
+    /// Uses the Proof of Possession ciphersuite.
     pub fn new(msg: &[u8], sk: &SecretKey) -> Self {
-        let hash_point = hash_to_curve_g2(msg);
-        let sig = g2mul(&hash_point, sk.as_raw());
+        Self::new_with_ciphersuite::<ProofOfPossessionScheme>(msg, sk)
+    }
+
+    /// Sign
+    ///
+    /// Instantiate a new Signature from a message and a SecretKey using the given Ciphersuite.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3
+    pub fn new_with_ciphersuite<C: Ciphersuite>(msg: &[u8], sk: &SecretKey) -> Self {
+        let hash_point = if C::AUGMENT_MESSAGES {
+            hash_message::<C>(msg, &PublicKey::from_secret_key(sk))
+        } else {
+            hash_to_curve_g2_with_dst(msg, C::DST)
+        };
+        Self::new_hashed(&hash_point, sk)
+    }
+
+    /// CoreSign
+    ///
+    /// Instantiate a new Signature from a message and a SecretKey using a custom
+    /// domain separation tag. Errors if the DST is empty.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-2.7
+    pub fn new_with_dst(msg: &[u8], sk: &SecretKey, dst: &[u8]) -> Result<Self, Error> {
+        let dst = validate_dst(dst)?;
+        let hash_point = hash_to_curve_g2_with_dst(msg, dst);
+        Ok(Self::new_hashed(&hash_point, sk))
+    }
+
+    /// CoreSign
+    ///
+    /// Instantiate a new Signature from a message which has already been hashed to curve.
+    pub fn new_prehashed(hashed_message: &HashedMessage, sk: &SecretKey) -> Self {
+        Self::new_hashed(hashed_message.point(), sk)
+    }
+
+    // Sign a message which has already been hashed to curve.
+    fn new_hashed(hash_point: &GroupG2, sk: &SecretKey) -> Self {
+        let sig = g2mul(hash_point, sk.as_raw());
         Self { point: sig }
     }
 
     /// CoreVerify
     ///
     /// Verifies the Signature against a PublicKey.
+    /// Uses the Proof of Possession ciphersuite.
     /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-02#section-3.3
     pub fn verify(&self, msg: &[u8], pk: &PublicKey) -> bool {
+        self.try_verify(msg, pk).is_ok()
+    }
+
+    /// CoreVerify
+    ///
+    /// As `verify` returning the reason verification failed.
+    pub fn try_verify(&self, msg: &[u8], pk: &PublicKey) -> Result<(), VerifyError> {
+        self.try_verify_with_ciphersuite::<ProofOfPossessionScheme>(msg, pk)
+    }
+
+    /// CoreVerify
+    ///
+    /// As `verify` against a PreparedPublicKey, which may be prepared from an AggregatePublicKey.
+    pub fn verify_prepared(&self, msg: &[u8], pk: &PreparedPublicKey) -> bool {
+        self.try_verify_prepared(msg, pk).is_ok()
+    }
+
+    /// CoreVerify
+    ///
+    /// As `verify_prepared` returning the reason verification failed.
+    pub fn try_verify_prepared(
+        &self,
+        msg: &[u8],
+        pk: &PreparedPublicKey,
+    ) -> Result<(), VerifyError> {
+        self.verify_hashed(hash_message::<ProofOfPossessionScheme>(msg, pk), pk)
+    }
+
+    /// Verify
+    ///
+    /// Verifies the Signature against a PublicKey using the given Ciphersuite.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3
+    pub fn verify_with_ciphersuite<C: Ciphersuite>(&self, msg: &[u8], pk: &PublicKey) -> bool {
+        self.try_verify_with_ciphersuite::<C>(msg, pk).is_ok()
+    }
+
+    /// Verify
+    ///
+    /// As `verify_with_ciphersuite` returning the reason verification failed.
+    pub fn try_verify_with_ciphersuite<C: Ciphersuite>(
+        &self,
+        msg: &[u8],
+        pk: &PublicKey,
+    ) -> Result<(), VerifyError> {
+        self.verify_hashed(hash_message::<C>(msg, pk), pk)
+    }
+
+    /// CoreVerify
+    ///
+    /// Verifies the Signature against a PublicKey, PreparedPublicKey or AggregatePublicKey using
+    /// a custom domain separation tag. Returns false if the DST is empty.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-2.7
+    pub fn verify_with_dst<K: VerifyingKey>(&self, msg: &[u8], pk: &K, dst: &[u8]) -> bool {
+        self.try_verify_with_dst(msg, pk, dst).is_ok()
+    }
+
+    /// CoreVerify
+    ///
+    /// As `verify_with_dst` returning the reason verification failed.
+    pub fn try_verify_with_dst<K: VerifyingKey>(
+        &self,
+        msg: &[u8],
+        pk: &K,
+        dst: &[u8],
+    ) -> Result<(), VerifyError> {
+        let dst = validate_dst(dst).map_err(|_| VerifyError::InvalidDst)?;
+        self.verify_hashed(hash_to_curve_g2_with_dst(msg, dst), pk)
+    }
+
+    /// CoreVerify
+    ///
+    /// Verifies the Signature against a PublicKey, PreparedPublicKey or AggregatePublicKey and a
+    /// message which has already been hashed to curve.
+    pub fn verify_prehashed<K: VerifyingKey>(
+        &self,
+        hashed_message: &HashedMessage,
+        pk: &K,
+    ) -> bool {
+        self.try_verify_prehashed(hashed_message, pk).is_ok()
+    }
+
+    /// CoreVerify
+    ///
+    /// As `verify_prehashed` returning the reason verification failed.
+    pub fn try_verify_prehashed<K: VerifyingKey>(
+        &self,
+        hashed_message: &HashedMessage,
+        pk: &K,
+    ) -> Result<(), VerifyError> {
+        self.verify_hashed(hashed_message.point().clone(), pk)
+    }
+
+    // Verifies the Signature against a PublicKey and a message which has already been
+    // hashed to curve.
+    fn verify_hashed(
+        &self,
+        mut msg_hash_point: GroupG2,
+        pk: &impl VerifyingKey,
+    ) -> Result<(), VerifyError> {
         // Signature Subgroup checks
         if !subgroup_check_g2(&self.point) {
-            return false;
+            return Err(VerifyError::SignatureNotInSubgroup);
+        }
+
+        // PreparedPublicKeys may be the point at infinity
+        if pk.point().is_infinity() {
+            return Err(VerifyError::InfinityPublicKey);
         }
 
-        let mut msg_hash_point = hash_to_curve_g2(msg);
         msg_hash_point.affine();
 
         // Faster ate2 evaualtion checks e(S, -G1) * e(H, PK) == 1
         let mut generator_g1_negative = amcl_utils::GroupG1::generator();
         generator_g1_negative.neg();
-        ate2_evaluation(&self.point, &generator_g1_negative, &msg_hash_point, &pk.point)
+        if !ate2_evaluation(
+            &self.point,
+            &generator_g1_negative,
+            &msg_hash_point,
+            &pk.affine_point(),
+        ) {
+            return Err(VerifyError::PairingMismatch);
+        }
+        Ok(())
     }
 
     /// Instantiate a Signature from compressed bytes.
-    pub fn from_bytes(bytes: &[u8]) -> Result<Signature, AmclError> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Signature, Error> {
         let point = decompress_g2(bytes)?;
         Ok(Self { point })
     }
@@ -49,6 +208,173 @@ impl Signature {
     pub fn as_bytes(&self) -> [u8; G2_BYTES] {
         compress_g2(&self.point)
     }
+
+    /// Combine at least `threshold` partial Signatures into the Signature of the split SecretKey.
+    ///
+    /// S = sum(lambda_i * S_i) where lambda_i are the Lagrange coefficients at 0.
+    /// Partial Signatures MUST NOT use the MessageAugmentationScheme, as each share would hash
+    /// the message with a different PublicKey.
+    pub fn combine_threshold(shares: &[(u32, Signature)]) -> Result<Self, ThresholdError> {
+        let indices: Vec<u32> = shares.iter().map(|(index, _)| *index).collect();
+        let coefficients = lagrange_coefficients(&indices)?;
+
+        let mut point = GroupG2::new();
+        for ((_, share), coefficient) in shares.iter().zip(coefficients.iter()) {
+            point.add(&g2mul(&share.point, coefficient));
+        }
+        Ok(Self { point })
+    }
+}
+
+/// A BLS Signature for the minimal-signature-size variant.
+///
+/// The Signature is a point in G1 and is verified against a MinSigPublicKey which is a point in G2.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct MinSigSignature {
+    pub point: GroupG1,
+}
+
+impl MinSigSignature {
+    /// Instantiate a new MinSigSignature from a message and a SecretKey.
+    ///
+    /// Uses the Proof of Possession ciphersuite.
+    pub fn new(msg: &[u8], sk: &SecretKey) -> Self {
+        Self::new_with_ciphersuite::<ProofOfPossessionScheme>(msg, sk)
+    }
+
+    /// Sign
+    ///
+    /// Instantiate a new MinSigSignature from a message and a SecretKey using the given
+    /// Ciphersuite.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3
+    pub fn new_with_ciphersuite<C: Ciphersuite>(msg: &[u8], sk: &SecretKey) -> Self {
+        let hash_point = if C::AUGMENT_MESSAGES {
+            hash_message_min_sig::<C>(msg, &MinSigPublicKey::from_secret_key(sk))
+        } else {
+            hash_to_curve_g1_with_dst(msg, C::MIN_SIG_DST)
+        };
+        Self::new_hashed(&hash_point, sk)
+    }
+
+    /// CoreSign
+    ///
+    /// Instantiate a new MinSigSignature from a message and a SecretKey using a custom
+    /// domain separation tag. Errors if the DST is empty.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-2.7
+    pub fn new_with_dst(msg: &[u8], sk: &SecretKey, dst: &[u8]) -> Result<Self, Error> {
+        let dst = validate_dst(dst)?;
+        let hash_point = hash_to_curve_g1_with_dst(msg, dst);
+        Ok(Self::new_hashed(&hash_point, sk))
+    }
+
+    // Sign a message which has already been hashed to curve.
+    fn new_hashed(hash_point: &GroupG1, sk: &SecretKey) -> Self {
+        let sig = g1mul(hash_point, sk.as_raw());
+        Self { point: sig }
+    }
+
+    /// CoreVerify
+    ///
+    /// Verifies the MinSigSignature against a MinSigPublicKey.
+    /// Uses the Proof of Possession ciphersuite.
+    pub fn verify(&self, msg: &[u8], pk: &MinSigPublicKey) -> bool {
+        self.try_verify(msg, pk).is_ok()
+    }
+
+    /// CoreVerify
+    ///
+    /// As `verify` returning the reason verification failed.
+    pub fn try_verify(&self, msg: &[u8], pk: &MinSigPublicKey) -> Result<(), VerifyError> {
+        self.try_verify_with_ciphersuite::<ProofOfPossessionScheme>(msg, pk)
+    }
+
+    /// Verify
+    ///
+    /// Verifies the MinSigSignature against a MinSigPublicKey using the given Ciphersuite.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-3
+    pub fn verify_with_ciphersuite<C: Ciphersuite>(
+        &self,
+        msg: &[u8],
+        pk: &MinSigPublicKey,
+    ) -> bool {
+        self.try_verify_with_ciphersuite::<C>(msg, pk).is_ok()
+    }
+
+    /// Verify
+    ///
+    /// As `verify_with_ciphersuite` returning the reason verification failed.
+    pub fn try_verify_with_ciphersuite<C: Ciphersuite>(
+        &self,
+        msg: &[u8],
+        pk: &MinSigPublicKey,
+    ) -> Result<(), VerifyError> {
+        self.verify_hashed(hash_message_min_sig::<C>(msg, pk), pk)
+    }
+
+    /// CoreVerify
+    ///
+    /// Verifies the MinSigSignature against a MinSigPublicKey using a custom domain separation
+    /// tag. Returns false if the DST is empty.
+    /// https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-2.7
+    pub fn verify_with_dst(&self, msg: &[u8], pk: &MinSigPublicKey, dst: &[u8]) -> bool {
+        self.try_verify_with_dst(msg, pk, dst).is_ok()
+    }
+
+    /// CoreVerify
+    ///
+    /// As `verify_with_dst` returning the reason verification failed.
+    pub fn try_verify_with_dst(
+        &self,
+        msg: &[u8],
+        pk: &MinSigPublicKey,
+        dst: &[u8],
+    ) -> Result<(), VerifyError> {
+        let dst = validate_dst(dst).map_err(|_| VerifyError::InvalidDst)?;
+        self.verify_hashed(hash_to_curve_g1_with_dst(msg, dst), pk)
+    }
+
+    // Verifies the MinSigSignature against a MinSigPublicKey and a message which has already
+    // been hashed to curve.
+    fn verify_hashed(
+        &self,
+        mut msg_hash_point: GroupG1,
+        pk: &MinSigPublicKey,
+    ) -> Result<(), VerifyError> {
+        // Signature Subgroup checks
+        if !subgroup_check_g1(&self.point) {
+            return Err(VerifyError::SignatureNotInSubgroup);
+        }
+
+        // MinSigPublicKeys from `from_bytes_unchecked` may be the point at infinity
+        if pk.point.is_infinity() {
+            return Err(VerifyError::InfinityPublicKey);
+        }
+
+        // Points must be affine for pairing
+        let mut pk_point = pk.point.clone();
+        pk_point.affine();
+        msg_hash_point.affine();
+
+        // Faster ate2 evaualtion checks e(PK, H) * e(-G2, S) == 1
+        let mut generator_g2_negative = amcl_utils::GroupG2::generator();
+        generator_g2_negative.neg();
+        if !ate2_evaluation(&pk_point, &msg_hash_point, &generator_g2_negative, &self.point) {
+            return Err(VerifyError::PairingMismatch);
+        }
+        Ok(())
+    }
+
+    /// Instantiate a MinSigSignature from compressed bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<MinSigSignature, Error> {
+        let point = decompress_g1(bytes)?;
+        Ok(Self { point })
+    }
+
+    /// Compress the MinSigSignature as bytes.
+    pub fn as_bytes(&self) -> [u8; G1_BYTES] {
+        compress_g1(&self.point)
+    }
 }
 
 #[cfg(test)]
@@ -56,6 +382,7 @@ mod tests {
     extern crate hex;
     extern crate rand;
 
+    use super::super::amcl_utils::non_subgroup_g2;
     use super::super::keys::Keypair;
     use super::*;
 
@@ -99,6 +426,161 @@ mod tests {
         assert_eq!(sig.verify(&msg.as_bytes(), &vk), false);
     }
 
+    #[test]
+    fn test_sign_verify_with_dst() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = "cats".as_bytes();
+        let dst = b"MY_APPLICATION_BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+        let sig = Signature::new_with_dst(msg, &keypair.sk, dst).unwrap();
+        assert!(sig.verify_with_dst(msg, &keypair.pk, dst));
+
+        // Signatures are only valid for their own DST
+        assert!(!sig.verify(msg, &keypair.pk));
+        assert!(!sig.verify_with_dst(msg, &keypair.pk, b"OTHER_DST"));
+        assert!(!Signature::new(msg, &keypair.sk).verify_with_dst(msg, &keypair.pk, dst));
+
+        // The Ethereum DST is equivalent to the default ciphersuite
+        let sig = Signature::new_with_dst(msg, &keypair.sk, ProofOfPossessionScheme::DST).unwrap();
+        assert_eq!(sig, Signature::new(msg, &keypair.sk));
+    }
+
+    #[test]
+    fn test_sign_verify_prehashed() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = "cats".as_bytes();
+        let hashed_message = HashedMessage::new(msg);
+
+        let sig = Signature::new_prehashed(&hashed_message, &keypair.sk);
+        assert!(sig.verify_prehashed(&hashed_message, &keypair.pk));
+        assert!(sig.verify(msg, &keypair.pk));
+
+        let other_keypair = Keypair::random(&mut rand::thread_rng());
+        assert!(!sig.verify_prehashed(&HashedMessage::new(b"dogs"), &keypair.pk));
+        assert!(!sig.verify_prehashed(&hashed_message, &other_keypair.pk));
+    }
+
+    #[test]
+    fn test_sign_verify_with_invalid_dst() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = "cats".as_bytes();
+
+        assert_eq!(Signature::new_with_dst(msg, &keypair.sk, &[]), Err(Error::InvalidDst));
+
+        let sig = Signature::new(msg, &keypair.sk);
+        assert!(!sig.verify_with_dst(msg, &keypair.pk, &[]));
+    }
+
+    #[test]
+    fn test_try_verify() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = "cats".as_bytes();
+        let sig = Signature::new(msg, &keypair.sk);
+
+        assert_eq!(sig.try_verify(msg, &keypair.pk), Ok(()));
+        assert_eq!(sig.try_verify(b"dogs", &keypair.pk), Err(VerifyError::PairingMismatch));
+        assert_eq!(sig.try_verify_with_dst(msg, &keypair.pk, &[]), Err(VerifyError::InvalidDst));
+        assert_eq!(sig.try_verify_prehashed(&HashedMessage::new(msg), &keypair.pk), Ok(()));
+        assert_eq!(
+            sig.try_verify_prehashed(&HashedMessage::new(b"dogs"), &keypair.pk),
+            Err(VerifyError::PairingMismatch)
+        );
+
+        // Signature::from_bytes leaves the subgroup check to verification
+        let sig = Signature { point: non_subgroup_g2() };
+        assert_eq!(sig.try_verify(msg, &keypair.pk), Err(VerifyError::SignatureNotInSubgroup));
+        assert!(!sig.verify(msg, &keypair.pk));
+    }
+
+    #[test]
+    fn test_sign_verify_with_oversized_dst() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = "cats".as_bytes();
+        let dst = vec![b'A'; 300];
+
+        let sig = Signature::new_with_dst(msg, &keypair.sk, &dst).unwrap();
+        assert!(sig.verify_with_dst(msg, &keypair.pk, &dst));
+
+        // Oversized DSTs are hashed in full rather than truncated
+        let mut other_dst = dst.clone();
+        other_dst[299] = b'B';
+        assert!(!sig.verify_with_dst(msg, &keypair.pk, &other_dst));
+    }
+
+    #[test]
+    fn test_min_sig_sign_verify() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let pk = MinSigPublicKey::from_secret_key(&keypair.sk);
+
+        for m in ["", "a", "an example"] {
+            let bytes = m.as_bytes();
+            let sig = MinSigSignature::new(bytes, &keypair.sk);
+            assert!(sig.verify(bytes, &pk));
+
+            // Test serializing, then deserializing the signature
+            let sig_bytes = sig.as_bytes();
+            assert_eq!(sig_bytes.len(), 48);
+            let new_sig = MinSigSignature::from_bytes(&sig_bytes).unwrap();
+            assert_eq!(new_sig, sig);
+            assert!(new_sig.verify(bytes, &pk));
+
+            // Wrong message
+            assert!(!sig.verify(b"other message", &pk));
+        }
+    }
+
+    #[test]
+    fn test_min_sig_verify_infinity() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let msg = "cats".as_bytes();
+
+        // Signature of infinity should fail
+        let sig = MinSigSignature { point: GroupG1::new() };
+        assert!(!sig.verify(msg, &MinSigPublicKey::from_secret_key(&keypair.sk)));
+    }
+
+    #[test]
+    fn test_min_sig_sign_verify_with_dst() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let pk = MinSigPublicKey::from_secret_key(&keypair.sk);
+        let msg = "cats".as_bytes();
+        let dst = b"MY_APPLICATION_BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+        let sig = MinSigSignature::new_with_dst(msg, &keypair.sk, dst).unwrap();
+        assert!(sig.verify_with_dst(msg, &pk, dst));
+        assert_eq!(sig.try_verify_with_dst(msg, &pk, dst), Ok(()));
+
+        // Signatures are only valid for their own DST
+        assert!(!sig.verify(msg, &pk));
+        assert_eq!(sig.try_verify_with_dst(msg, &pk, &[]), Err(VerifyError::InvalidDst));
+        assert_eq!(MinSigSignature::new_with_dst(msg, &keypair.sk, &[]), Err(Error::InvalidDst));
+
+        // The Ethereum DST is equivalent to the default ciphersuite
+        let sig =
+            MinSigSignature::new_with_dst(msg, &keypair.sk, ProofOfPossessionScheme::MIN_SIG_DST)
+                .unwrap();
+        assert_eq!(sig, MinSigSignature::new(msg, &keypair.sk));
+        assert_eq!(sig.try_verify(b"dogs", &pk), Err(VerifyError::PairingMismatch));
+    }
+
+    #[test]
+    fn test_min_sig_readme() {
+        use super::super::aggregates::MinSigAggregateSignature;
+
+        // This is an exact replica of the README.md at the top level.
+        let sk = SecretKey::random(&mut rand::thread_rng());
+        let pk = MinSigPublicKey::from_secret_key(&sk);
+
+        // Sign and verify a message.
+        let message = "cats".as_bytes();
+        let signature = MinSigSignature::new(message, &sk);
+        assert!(signature.verify(message, &pk));
+
+        // Aggregate signatures in the same way as the default variant.
+        let aggregate_signature = MinSigAggregateSignature::aggregate(&[&signature]);
+        assert!(aggregate_signature.fast_aggregate_verify(message, &[&pk]));
+    }
+
     #[test]
     fn test_readme() {
         // This is an exact replica of the README.md at the top level.
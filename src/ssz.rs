@@ -0,0 +1,190 @@
+extern crate amcl;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use super::aggregates::AggregateSignature;
+use super::amcl_utils::{G1_BYTES, G2_BYTES};
+use super::compressed::{PublicKeyBytes, SignatureBytes};
+use super::error::Error;
+use super::keys::PublicKey;
+use super::signature::Signature;
+
+use amcl::hash256::HASH256;
+
+// SSZ Constants
+/// Length in bytes of a merkleization chunk.
+pub const BYTES_PER_CHUNK: usize = 32;
+
+/// Fixed-size SSZ encoding and merkleization of a byte vector, e.g. `BLSPubkey` (Bytes48) and
+/// `BLSSignature` (Bytes96).
+pub trait Ssz: Sized {
+    /// Length in bytes of the SSZ encoding.
+    const SSZ_FIXED_LEN: usize;
+
+    /// Append the SSZ encoding to `buf`.
+    fn ssz_append(&self, buf: &mut Vec<u8>);
+
+    /// Decode from SSZ bytes, with the same validation as `from_bytes`.
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Encode as SSZ bytes.
+    fn as_ssz_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(Self::SSZ_FIXED_LEN);
+        self.ssz_append(&mut buf);
+        buf
+    }
+
+    /// The SHA-256 merkle root of the SSZ encoding packed into 32 byte chunks.
+    fn hash_tree_root(&self) -> [u8; 32] {
+        merkleize_bytes(&self.as_ssz_bytes())
+    }
+}
+
+// Implements Ssz with `as_bytes` and `from_bytes`.
+macro_rules! impl_ssz {
+    ($type:ident, $len:expr) => {
+        impl Ssz for $type {
+            const SSZ_FIXED_LEN: usize = $len;
+
+            fn ssz_append(&self, buf: &mut Vec<u8>) {
+                buf.extend_from_slice(&self.as_bytes());
+            }
+
+            fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, Error> {
+                $type::from_bytes(bytes)
+            }
+        }
+    };
+}
+
+impl_ssz!(PublicKey, G1_BYTES);
+impl_ssz!(Signature, G2_BYTES);
+impl_ssz!(AggregateSignature, G2_BYTES);
+impl_ssz!(PublicKeyBytes, G1_BYTES);
+impl_ssz!(SignatureBytes, G2_BYTES);
+
+// Pack bytes into zero padded chunks and merkleize them, padding the leaves to a power of two.
+fn merkleize_bytes(bytes: &[u8]) -> [u8; 32] {
+    let mut chunks: Vec<[u8; BYTES_PER_CHUNK]> = bytes
+        .chunks(BYTES_PER_CHUNK)
+        .map(|bytes| {
+            let mut chunk = [0u8; BYTES_PER_CHUNK];
+            chunk[..bytes.len()].copy_from_slice(bytes);
+            chunk
+        })
+        .collect();
+    if chunks.is_empty() {
+        return [0u8; 32];
+    }
+    chunks.resize(chunks.len().next_power_of_two(), [0u8; BYTES_PER_CHUNK]);
+
+    while chunks.len() > 1 {
+        chunks = chunks.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+    }
+    chunks[0]
+}
+
+// SHA-256(left || right)
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hash256 = HASH256::new();
+    hash256.init();
+    hash256.process_array(left);
+    hash256.process_array(right);
+    hash256.hash()
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate hex;
+    extern crate rand;
+
+    use super::super::keys::Keypair;
+    use super::*;
+
+    #[test]
+    fn test_merkleize_bytes() {
+        // Zero hashes of depth 1 and 2
+        assert_eq!(
+            merkleize_bytes(&[0; G1_BYTES]).to_vec(),
+            hex::decode("f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b")
+                .unwrap()
+        );
+        assert_eq!(
+            merkleize_bytes(&[0; G2_BYTES]).to_vec(),
+            hex::decode("db56114e00fdd4c1f85c892bf35ac9a89289aaecb1ebd0a96cde606a748b5d71")
+                .unwrap()
+        );
+
+        let bytes: Vec<u8> = (0..G2_BYTES as u8).collect();
+        assert_eq!(
+            merkleize_bytes(&bytes[..G1_BYTES]).to_vec(),
+            hex::decode("b976c9abe97b4f03d7e4058246713687379d2718a829ab66e2a93aa924e43c1d")
+                .unwrap()
+        );
+        assert_eq!(
+            merkleize_bytes(&bytes).to_vec(),
+            hex::decode("17c8d5caa3d7162e8dada90de6e741783f3d73736498c11eba34974fbf5464f3")
+                .unwrap()
+        );
+
+        // A single chunk is its own root
+        assert_eq!(merkleize_bytes(&[1; 32]), [1; 32]);
+    }
+
+    #[test]
+    fn test_ssz_round_trip() {
+        let keypair = Keypair::random(&mut rand::thread_rng());
+        let signature = Signature::new(b"cats", &keypair.sk);
+        let aggregate_signature = AggregateSignature::aggregate(&[&signature]);
+
+        let encoded = keypair.pk.as_ssz_bytes();
+        assert_eq!(encoded.len(), PublicKey::SSZ_FIXED_LEN);
+        assert_eq!(encoded, keypair.pk.as_bytes().to_vec());
+        assert_eq!(PublicKey::from_ssz_bytes(&encoded), Ok(keypair.pk.clone()));
+
+        let encoded = signature.as_ssz_bytes();
+        assert_eq!(encoded.len(), Signature::SSZ_FIXED_LEN);
+        assert_eq!(Signature::from_ssz_bytes(&encoded), Ok(signature.clone()));
+        assert_eq!(AggregateSignature::from_ssz_bytes(&encoded), Ok(aggregate_signature.clone()));
+
+        // Lazy byte forms encode and merkleize identically
+        let public_key_bytes = PublicKeyBytes::from(&keypair.pk);
+        let signature_bytes = SignatureBytes::from(&signature);
+        assert_eq!(public_key_bytes.as_ssz_bytes(), keypair.pk.as_ssz_bytes());
+        assert_eq!(public_key_bytes.hash_tree_root(), keypair.pk.hash_tree_root());
+        assert_eq!(
+            PublicKeyBytes::from_ssz_bytes(&keypair.pk.as_ssz_bytes()),
+            Ok(public_key_bytes)
+        );
+        assert_eq!(signature_bytes.hash_tree_root(), signature.hash_tree_root());
+        assert_eq!(aggregate_signature.hash_tree_root(), signature.hash_tree_root());
+        assert_eq!(SignatureBytes::from_ssz_bytes(&signature.as_ssz_bytes()), Ok(signature_bytes));
+    }
+
+    #[test]
+    fn test_ssz_invalid() {
+        assert_eq!(
+            PublicKey::from_ssz_bytes(&[0; G1_BYTES - 1]),
+            Err(Error::InvalidLength { expected: G1_BYTES })
+        );
+        assert_eq!(
+            PublicKeyBytes::from_ssz_bytes(&[0; G2_BYTES]),
+            Err(Error::InvalidLength { expected: G1_BYTES })
+        );
+        assert_eq!(
+            Signature::from_ssz_bytes(&[0; G1_BYTES]),
+            Err(Error::InvalidLength { expected: G2_BYTES })
+        );
+        assert_eq!(
+            SignatureBytes::from_ssz_bytes(&[]),
+            Err(Error::InvalidLength { expected: G2_BYTES })
+        );
+
+        // PublicKeys are validated, the lazy byte form is not
+        let mut infinity = [0u8; G1_BYTES];
+        infinity[0] = 0xc0;
+        assert_eq!(PublicKey::from_ssz_bytes(&infinity), Err(Error::InfinityPublicKey));
+        assert!(PublicKeyBytes::from_ssz_bytes(&infinity).is_ok());
+    }
+}
@@ -0,0 +1,533 @@
+extern crate amcl;
+extern crate rand;
+extern crate zeroize;
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fmt;
+
+use self::zeroize::Zeroize;
+use super::amcl_utils::{g1mul, Big, GroupG1, CURVE_ORDER, MODBYTES};
+use super::keys::{PublicKey, SecretKey};
+
+use rand::Rng;
+
+/// Errors when splitting a SecretKey or combining shares.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThresholdError {
+    /// The threshold MUST be at least 1 and at most the number of shares.
+    InvalidThreshold,
+    /// Share indices MUST be non-zero, index 0 is the secret itself.
+    InvalidIndex,
+    /// Each share index MUST only be used once.
+    DuplicateIndex(u32),
+    /// At least one share is required to combine.
+    EmptyShares,
+    /// At least one commitment is required to derive a PublicKey share.
+    EmptyCommitments,
+}
+
+impl fmt::Display for ThresholdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThresholdError::InvalidThreshold => {
+                write!(f, "threshold must be at least 1 and at most the number of shares")
+            }
+            ThresholdError::InvalidIndex => write!(f, "share index must be non-zero"),
+            ThresholdError::DuplicateIndex(index) => write!(f, "duplicate share index {}", index),
+            ThresholdError::EmptyShares => write!(f, "no shares to combine"),
+            ThresholdError::EmptyCommitments => write!(f, "no commitments"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ThresholdError {}
+
+/// SecretKey shares with their indices along with Feldman commitments to the polynomial.
+pub type VerifiableShares = (Vec<(u32, SecretKey)>, Vec<PublicKey>);
+
+/// A random polynomial over the scalar field of degree `threshold - 1`.
+///
+/// f(x) = a_0 + a_1 * x + ... + a_{t-1} * x^{t-1} where a_0 is the secret.
+pub(crate) struct Polynomial {
+    coefficients: Vec<Big>,
+}
+
+impl Polynomial {
+    /// Generate a random polynomial with `secret` as its constant term.
+    pub(crate) fn random<R: Rng + ?Sized>(
+        secret: &SecretKey,
+        threshold: usize,
+        rng: &mut R,
+    ) -> Self {
+        let mut coefficients = Vec::with_capacity(threshold);
+        coefficients.push(secret.as_raw().clone());
+        for _ in 1..threshold {
+            coefficients.push(SecretKey::random(rng).as_raw().clone());
+        }
+        Self { coefficients }
+    }
+
+    /// Recover the polynomial of degree `points.len() - 1` through the points (x, f(x)).
+    ///
+    /// f(x) = sum(y_i * prod_{j != i} (x - x_j) / (x_i - x_j)) mod r
+    pub(crate) fn interpolate(points: &[(u32, Big)]) -> Result<Self, ThresholdError> {
+        let indices: Vec<u32> = points.iter().map(|(x, _)| *x).collect();
+        lagrange_coefficients(&indices)?;
+
+        let r = Big::new_ints(&CURVE_ORDER);
+        let mut coefficients = vec![Big::new(); points.len()];
+        for (x_i, y_i) in points.iter() {
+            // Coefficients of prod_{j != i} (x - x_j), lowest degree first
+            let mut basis = vec![Big::new_int(1)];
+            let mut denominator = Big::new_int(1);
+            for (x_j, _) in points.iter().filter(|(x_j, _)| x_j != x_i) {
+                let negative_x_j = Big::modneg(&big_from_u32(*x_j), &r);
+                let mut product = vec![Big::new(); basis.len() + 1];
+                for (k, b_k) in basis.iter().enumerate() {
+                    product[k].add(&Big::modmul(b_k, &negative_x_j, &r));
+                    product[k].rmod(&r);
+                    product[k + 1].add(b_k);
+                    product[k + 1].rmod(&r);
+                }
+                basis = product;
+                denominator = Big::modmul(&denominator, &difference(*x_i, *x_j), &r);
+            }
+
+            denominator.invmodp(&r);
+            let scale = Big::modmul(y_i, &denominator, &r);
+            for (coefficient, b_k) in coefficients.iter_mut().zip(basis.iter()) {
+                coefficient.add(&Big::modmul(b_k, &scale, &r));
+                coefficient.rmod(&r);
+            }
+        }
+        Ok(Self { coefficients })
+    }
+
+    /// Feldman commitments C_k = a_k * G1 to each coefficient of the polynomial.
+    pub(crate) fn commitments(&self) -> Vec<PublicKey> {
+        self.coefficients
+            .iter()
+            .map(|coefficient| {
+                PublicKey::from_secret_key(&SecretKey::from_raw(coefficient.clone()))
+            })
+            .collect()
+    }
+
+    /// Pedersen commitments C_k = a_k * G1 + b_k * H to each coefficient of the polynomial,
+    /// where b_k are the coefficients of the `blinding` polynomial.
+    pub(crate) fn pedersen_commitments(
+        &self,
+        blinding: &Polynomial,
+        generator: &GroupG1,
+    ) -> Vec<PublicKey> {
+        self.commitments()
+            .into_iter()
+            .zip(blinding.coefficients.iter())
+            .map(|(mut commitment, b_k)| {
+                commitment.point.add(&g1mul(generator, b_k));
+                commitment
+            })
+            .collect()
+    }
+
+    /// The shares f(1), ..., f(n) of the polynomial.
+    pub(crate) fn shares(&self, n: usize) -> Vec<(u32, SecretKey)> {
+        (1..=n as u32).map(|index| (index, SecretKey::from_raw(self.evaluate(index)))).collect()
+    }
+
+    /// Evaluate f(x) mod r using Horner's method.
+    pub(crate) fn evaluate(&self, x: u32) -> Big {
+        let r = Big::new_ints(&CURVE_ORDER);
+        let x = big_from_u32(x);
+
+        let mut result = Big::new();
+        for coefficient in self.coefficients.iter().rev() {
+            result = Big::modmul(&result, &x, &r);
+            result.add(coefficient);
+            result.rmod(&r);
+        }
+        result
+    }
+}
+
+impl Drop for Polynomial {
+    fn drop(&mut self) {
+        for coefficient in self.coefficients.iter_mut() {
+            coefficient.w.zeroize();
+        }
+    }
+}
+
+/// Split a SecretKey into `n` shares, any `threshold` of which can recombine the secret.
+///
+/// Shares are evaluations of a random polynomial at the indices 1, ..., n.
+/// https://dl.acm.org/doi/10.1145/359168.359176
+pub(crate) fn split<R: Rng + ?Sized>(
+    sk: &SecretKey,
+    threshold: usize,
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<(u32, SecretKey)>, ThresholdError> {
+    Ok(split_polynomial(sk, threshold, n, rng)?.shares(n))
+}
+
+/// Split a SecretKey into `n` shares along with Feldman commitments to the polynomial.
+///
+/// The commitments allow each share holder to verify their share with `verify_share`.
+/// https://www.cs.umd.edu/~gasarch/TOPICS/secretsharing/feldmanVSS.pdf
+pub(crate) fn split_verifiable<R: Rng + ?Sized>(
+    sk: &SecretKey,
+    threshold: usize,
+    n: usize,
+    rng: &mut R,
+) -> Result<VerifiableShares, ThresholdError> {
+    let polynomial = split_polynomial(sk, threshold, n, rng)?;
+    Ok((polynomial.shares(n), polynomial.commitments()))
+}
+
+fn split_polynomial<R: Rng + ?Sized>(
+    sk: &SecretKey,
+    threshold: usize,
+    n: usize,
+    rng: &mut R,
+) -> Result<Polynomial, ThresholdError> {
+    if threshold == 0 || threshold > n || n > u32::MAX as usize {
+        return Err(ThresholdError::InvalidThreshold);
+    }
+    Ok(Polynomial::random(sk, threshold, rng))
+}
+
+/// Derive the PublicKey share of the participant at `index` from Feldman commitments.
+///
+/// PK_i = sum(C_k * i^k)
+/// The PublicKey of the split SecretKey is the first commitment, C_0.
+pub fn public_key_share(
+    index: u32,
+    commitments: &[PublicKey],
+) -> Result<PublicKey, ThresholdError> {
+    if index == 0 {
+        return Err(ThresholdError::InvalidIndex);
+    }
+    if commitments.is_empty() {
+        return Err(ThresholdError::EmptyCommitments);
+    }
+
+    // Horner's method in the exponent
+    let x = big_from_u32(index);
+    let mut point = GroupG1::new();
+    for commitment in commitments.iter().rev() {
+        point = g1mul(&point, &x);
+        point.add(&commitment.point);
+    }
+    Ok(PublicKey { point })
+}
+
+/// Verify a SecretKey share at `index` is consistent with the Feldman commitments.
+///
+/// share * G1 == sum(C_k * index^k)
+pub fn verify_share(index: u32, share: &SecretKey, commitments: &[PublicKey]) -> bool {
+    match public_key_share(index, commitments) {
+        Ok(public_key) => public_key == PublicKey::from_secret_key(share),
+        Err(_) => false,
+    }
+}
+
+// Convert a share index to a Big, `Big::new_int` would wrap on 32-bit targets.
+fn big_from_u32(x: u32) -> Big {
+    let mut bytes = [0u8; MODBYTES];
+    bytes[MODBYTES - 4..].copy_from_slice(&x.to_be_bytes());
+    Big::from_bytes(&bytes)
+}
+
+// (a - b) mod r
+fn difference(a: u32, b: u32) -> Big {
+    if a >= b {
+        big_from_u32(a - b)
+    } else {
+        Big::modneg(&big_from_u32(b - a), &Big::new_ints(&CURVE_ORDER))
+    }
+}
+
+/// Calculate the Lagrange coefficients at x = 0 for a set of share indices.
+///
+/// lambda_i = prod_{j != i} x_j / (x_j - x_i) mod r
+pub(crate) fn lagrange_coefficients(indices: &[u32]) -> Result<Vec<Big>, ThresholdError> {
+    if indices.is_empty() {
+        return Err(ThresholdError::EmptyShares);
+    }
+    for (i, index) in indices.iter().enumerate() {
+        if *index == 0 {
+            return Err(ThresholdError::InvalidIndex);
+        }
+        if indices[..i].contains(index) {
+            return Err(ThresholdError::DuplicateIndex(*index));
+        }
+    }
+
+    let r = Big::new_ints(&CURVE_ORDER);
+    let coefficients = indices
+        .iter()
+        .map(|x_i| {
+            let mut numerator = Big::new_int(1);
+            let mut denominator = Big::new_int(1);
+            for x_j in indices.iter().filter(|x_j| *x_j != x_i) {
+                numerator = Big::modmul(&numerator, &big_from_u32(*x_j), &r);
+
+                denominator = Big::modmul(&denominator, &difference(*x_j, *x_i), &r);
+            }
+            denominator.invmodp(&r);
+            Big::modmul(&numerator, &denominator, &r)
+        })
+        .collect();
+    Ok(coefficients)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::signature::Signature;
+    use super::*;
+
+    #[test]
+    fn test_split_and_combine_signatures() {
+        let mut rng = rand::thread_rng();
+        let sk = SecretKey::random(&mut rng);
+        let pk = PublicKey::from_secret_key(&sk);
+        let msg = b"threshold";
+        let expected = Signature::new(msg, &sk);
+
+        let shares = sk.split(3, 5, &mut rng).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert_eq!(shares.iter().map(|(index, _)| *index).collect::<Vec<u32>>(), [1, 2, 3, 4, 5]);
+
+        let partial_signatures: Vec<(u32, Signature)> =
+            shares.iter().map(|(index, share)| (*index, Signature::new(msg, share))).collect();
+        let public_key_shares: Vec<(u32, PublicKey)> = shares
+            .iter()
+            .map(|(index, share)| (*index, PublicKey::from_secret_key(share)))
+            .collect();
+
+        // Each partial signature verifies against its public key share
+        for ((_, sig), (_, pk_share)) in partial_signatures.iter().zip(public_key_shares.iter()) {
+            assert!(sig.verify(msg, pk_share));
+            assert!(!sig.verify(msg, &pk));
+        }
+
+        // Any 3 or more shares recover the signature and public key
+        let subsets: &[&[usize]] =
+            &[&[0, 1, 2], &[2, 3, 4], &[4, 0, 2], &[1, 3, 4], &[0, 1, 2, 3], &[0, 1, 2, 3, 4]];
+        for subset in subsets {
+            let signatures: Vec<(u32, Signature)> =
+                subset.iter().map(|i| partial_signatures[*i].clone()).collect();
+            let combined = Signature::combine_threshold(&signatures).unwrap();
+            assert_eq!(combined, expected);
+            assert!(combined.verify(msg, &pk));
+
+            let public_keys: Vec<(u32, PublicKey)> =
+                subset.iter().map(|i| public_key_shares[*i].clone()).collect();
+            assert_eq!(PublicKey::combine_threshold(&public_keys).unwrap(), pk);
+        }
+
+        // Fewer than 3 shares do not
+        let combined = Signature::combine_threshold(&partial_signatures[..2]).unwrap();
+        assert_ne!(combined, expected);
+        assert!(!combined.verify(msg, &pk));
+        assert_ne!(PublicKey::combine_threshold(&public_key_shares[..2]).unwrap(), pk);
+    }
+
+    #[test]
+    fn test_split_threshold_one() {
+        let mut rng = rand::thread_rng();
+        let sk = SecretKey::random(&mut rng);
+        let shares = sk.split(1, 3, &mut rng).unwrap();
+        for (_, share) in shares.iter() {
+            assert_eq!(*share, sk);
+        }
+    }
+
+    #[test]
+    fn test_split_invalid_threshold() {
+        let mut rng = rand::thread_rng();
+        let sk = SecretKey::random(&mut rng);
+        assert_eq!(sk.split(0, 3, &mut rng).unwrap_err(), ThresholdError::InvalidThreshold);
+        assert_eq!(sk.split(4, 3, &mut rng).unwrap_err(), ThresholdError::InvalidThreshold);
+        assert_eq!(sk.split(0, 0, &mut rng).unwrap_err(), ThresholdError::InvalidThreshold);
+    }
+
+    #[test]
+    fn test_combine_invalid_indices() {
+        let mut rng = rand::thread_rng();
+        let sk = SecretKey::random(&mut rng);
+        let sig = Signature::new(b"msg", &sk);
+
+        assert_eq!(Signature::combine_threshold(&[]), Err(ThresholdError::EmptyShares));
+        assert_eq!(
+            Signature::combine_threshold(&[(0, sig.clone())]),
+            Err(ThresholdError::InvalidIndex)
+        );
+        assert_eq!(
+            Signature::combine_threshold(&[(1, sig.clone()), (2, sig.clone()), (1, sig)]),
+            Err(ThresholdError::DuplicateIndex(1))
+        );
+
+        let pk = PublicKey::from_secret_key(&sk);
+        assert_eq!(PublicKey::combine_threshold(&[]), Err(ThresholdError::EmptyShares));
+        assert_eq!(
+            PublicKey::combine_threshold(&[(3, pk.clone()), (3, pk)]),
+            Err(ThresholdError::DuplicateIndex(3))
+        );
+
+        let error: Box<dyn std::error::Error> = Box::new(ThresholdError::DuplicateIndex(3));
+        assert_eq!(error.to_string(), "duplicate share index 3");
+    }
+
+    #[test]
+    fn test_lagrange_coefficients() {
+        // With indices 1 and 2: lambda_1 = 2 and lambda_2 = -1
+        let r = Big::new_ints(&CURVE_ORDER);
+        let coefficients = lagrange_coefficients(&[1, 2]).unwrap();
+        assert_eq!(Big::comp(&coefficients[0], &Big::new_int(2)), 0);
+        assert_eq!(Big::comp(&coefficients[1], &Big::modneg(&Big::new_int(1), &r)), 0);
+
+        // A single share is the secret
+        let coefficients = lagrange_coefficients(&[7]).unwrap();
+        assert_eq!(Big::comp(&coefficients[0], &Big::new_int(1)), 0);
+    }
+
+    #[test]
+    fn test_polynomial_interpolate() {
+        let mut rng = rand::thread_rng();
+        let polynomial = Polynomial::random(&SecretKey::random(&mut rng), 3, &mut rng);
+
+        // Any 3 points recover the coefficients
+        let points: Vec<(u32, Big)> =
+            [5, 2, 9].iter().map(|x| (*x, polynomial.evaluate(*x))).collect();
+        let interpolated = Polynomial::interpolate(&points).unwrap();
+        assert_eq!(interpolated.coefficients.len(), 3);
+        for (a, b) in interpolated.coefficients.iter().zip(polynomial.coefficients.iter()) {
+            assert_eq!(Big::comp(a, b), 0);
+        }
+        assert_eq!(interpolated.commitments(), polynomial.commitments());
+
+        // Fewer points recover a different polynomial
+        let interpolated = Polynomial::interpolate(&points[..2]).unwrap();
+        assert_eq!(Big::comp(&interpolated.evaluate(5), &polynomial.evaluate(5)), 0);
+        assert_ne!(interpolated.commitments()[0], polynomial.commitments()[0]);
+
+        let duplicate = [(1, Big::new_int(1)), (1, Big::new_int(2))];
+        assert_eq!(
+            Polynomial::interpolate(&duplicate).err(),
+            Some(ThresholdError::DuplicateIndex(1))
+        );
+    }
+
+    #[test]
+    fn test_big_from_u32() {
+        // u32::MAX + 1 == 2^32
+        let mut x = big_from_u32(u32::MAX);
+        x.inc(1);
+        x.norm();
+        let mut expected = Big::new_int(1);
+        expected.shl(32);
+        assert_eq!(Big::comp(&x, &expected), 0);
+
+        assert_eq!(Big::comp(&big_from_u32(0), &Big::new()), 0);
+    }
+
+    #[test]
+    fn test_split_verifiable() {
+        let mut rng = rand::thread_rng();
+        let sk = SecretKey::random(&mut rng);
+        let pk = PublicKey::from_secret_key(&sk);
+
+        let (shares, commitments) = sk.split_verifiable(3, 5, &mut rng).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert_eq!(commitments.len(), 3);
+        assert_eq!(commitments[0], pk);
+
+        let public_key_shares: Vec<(u32, PublicKey)> = shares
+            .iter()
+            .map(|(index, share)| {
+                assert!(verify_share(*index, share, &commitments));
+                let public_key = public_key_share(*index, &commitments).unwrap();
+                assert_eq!(public_key, PublicKey::from_secret_key(share));
+                (*index, public_key)
+            })
+            .collect();
+        assert_eq!(PublicKey::combine_threshold(&public_key_shares[2..]).unwrap(), pk);
+
+        // Shares are only valid at their own index
+        assert!(!verify_share(2, &shares[0].1, &commitments));
+        assert!(!verify_share(0, &shares[0].1, &commitments));
+
+        // Tampered shares and commitments are detected
+        let tampered = SecretKey::random(&mut rng);
+        assert!(!verify_share(1, &tampered, &commitments));
+        assert!(!verify_share(1, &shares[0].1, &commitments[..2]));
+        let mut tampered_commitments = commitments.clone();
+        tampered_commitments[2] = PublicKey::from_secret_key(&tampered);
+        assert!(!verify_share(1, &shares[0].1, &tampered_commitments));
+    }
+
+    #[test]
+    fn test_split_verifiable_matches_shamir() {
+        // Both splits of the same SecretKey combine to the same Signature
+        let mut rng = rand::thread_rng();
+        let sk = SecretKey::random(&mut rng);
+        let (shares, _) = sk.split_verifiable(2, 2, &mut rng).unwrap();
+        let signatures: Vec<(u32, Signature)> =
+            shares.iter().map(|(index, share)| (*index, Signature::new(b"msg", share))).collect();
+        assert_eq!(Signature::combine_threshold(&signatures).unwrap(), Signature::new(b"msg", &sk));
+
+        assert_eq!(
+            sk.split_verifiable(3, 2, &mut rng).unwrap_err(),
+            ThresholdError::InvalidThreshold
+        );
+    }
+
+    #[test]
+    fn test_public_key_share_invalid() {
+        let pk = PublicKey::from_secret_key(&SecretKey::random(&mut rand::thread_rng()));
+        let commitments = vec![pk.clone()];
+        assert_eq!(public_key_share(0, &commitments), Err(ThresholdError::InvalidIndex));
+        assert_eq!(public_key_share(1, &[]), Err(ThresholdError::EmptyCommitments));
+
+        // A single commitment is the PublicKey of every share
+        assert_eq!(public_key_share(9, &commitments).unwrap(), pk);
+    }
+
+    #[test]
+    fn test_readme() {
+        // This is an exact replica of the README.md at the top level.
+        let sk = SecretKey::random(&mut rand::thread_rng());
+        let pk = PublicKey::from_secret_key(&sk);
+        let message = "cats".as_bytes();
+
+        // Split the SecretKey into 5 shares with a threshold of 3.
+        let shares = sk.split(3, 5, &mut rand::thread_rng()).unwrap();
+
+        // Any 3 shares produce partial signatures which combine to the full Signature.
+        let partial_signatures: Vec<(u32, Signature)> = shares[1..4]
+            .iter()
+            .map(|(index, share)| (*index, Signature::new(message, share)))
+            .collect();
+        let signature = Signature::combine_threshold(&partial_signatures).unwrap();
+        assert_eq!(signature, Signature::new(message, &sk));
+        assert!(signature.verify(message, &pk));
+
+        // Split with Feldman commitments so each share holder can verify their share.
+        let (shares, commitments) = sk.split_verifiable(3, 5, &mut rand::thread_rng()).unwrap();
+        for (index, share) in shares.iter() {
+            assert!(verify_share(*index, share, &commitments));
+            assert_eq!(
+                public_key_share(*index, &commitments).unwrap(),
+                PublicKey::from_secret_key(share)
+            );
+        }
+        assert_eq!(commitments[0], pk);
+    }
+}